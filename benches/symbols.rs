@@ -0,0 +1,108 @@
+//! Throughput of parsing DW symbols and looking up their RICs in the DW28 list, e.g. of the symbols
+//! from exchange feeds.
+//!
+//! Run with `cargo bench --bench symbols`.
+
+use criterion::{
+    black_box,
+    criterion_group,
+    criterion_main,
+    Criterion,
+    Throughput,
+};
+
+use regex::{
+    Regex,
+    RegexBuilder,
+};
+
+use settrade_dw::{
+    dw28::DW28,
+    instrument::dw::{
+        DWInfo,
+        DWInfoRef,
+    },
+};
+
+use std::str::FromStr;
+
+fn list_content ( ) -> String {
+    std::fs::read_to_string ( "tests/dw28/dw28_list_20201223.html" )
+        .expect ( "Failed to open file [dw28_list_20201223]" )
+}
+
+fn list_symbols ( content: &str ) -> Vec<String> {
+    DW28::parse_catalogue ( content )
+        .expect ( "Failed to parse the DW list" )
+        .symbols
+        .keys ( )
+        .map ( |symbol| symbol.to_string ( ) )
+        .collect ( )
+}
+
+fn bench_parse ( c: &mut Criterion ) {
+    let symbols = list_symbols ( &list_content ( ) );
+    let mut group = c.benchmark_group ( "parse" );
+    group.throughput ( Throughput::Elements ( symbols.len ( ) as u64 ) );
+    // the regex compiled per symbol before the hand-written parser, with the parts it captured
+    group.bench_function ( "per-call regex", |b| b.iter ( ||
+        symbols.iter ( )
+            .filter_map ( |symbol| {
+                let symbol = black_box ( symbol );
+                let captures = Regex::new ( r#"(\d{2})([CP])(\d{4})"# )
+                    .expect ( "Failed to create Regex of DW symbol." )
+                    .captures ( symbol )?;
+                let matched = captures.get ( 0 )?;
+                if matched.start ( ) == 0 || matched.end ( ) < symbol.len ( ) - 1 {
+                    return None;
+                }
+                Some ( (
+                    symbol.get ( 0..matched.start ( ) )?.to_owned ( ).into_boxed_str ( ),
+                    captures.get ( 1 )?.as_str ( ).parse::<u8> ( ).ok ( )?,
+                    captures.get ( 2 )?.as_str ( ).to_owned ( ),
+                    captures.get ( 3 )?.as_str ( ).to_owned ( ),
+                    symbol.chars ( ).nth ( matched.end ( ) )?,
+                ) )
+            } )
+            .count ( )
+    ) );
+    group.bench_function ( "DWInfo::from_str", |b| b.iter ( ||
+        symbols.iter ( )
+            .filter_map ( |symbol| DWInfo::from_str ( black_box ( symbol ) ).ok ( ) )
+            .count ( )
+    ) );
+    group.bench_function ( "DWInfoRef::parse", |b| b.iter ( ||
+        symbols.iter ( )
+            .filter_map ( |symbol| DWInfoRef::parse ( black_box ( symbol ) ).ok ( ) )
+            .count ( )
+    ) );
+    group.finish ( );
+}
+
+fn bench_listed_ric ( c: &mut Criterion ) {
+    let content = list_content ( );
+    // every 10th symbol, for the per-call regex to finish in reasonable time
+    let symbols = list_symbols ( &content ).into_iter ( ).step_by ( 10 ).collect::<Vec<String>> ( );
+    let mut group = c.benchmark_group ( "listed_ric" );
+    group.throughput ( Throughput::Elements ( symbols.len ( ) as u64 ) );
+    // the regex built per symbol before the static patterns
+    group.bench_function ( "per-call regex", |b| b.iter ( ||
+        symbols.iter ( )
+            .filter ( |symbol| RegexBuilder::new ( format ! ( r#"\{{([^\}}]*"security_code":"{}"[^\}}]*)\}}"#, symbol ).as_str ( ) )
+                .case_insensitive ( true )
+                .build ( )
+                .expect ( "Failed to create Regex pattern of the security_code data." )
+                .is_match ( black_box ( &content ) )
+            )
+            .count ( )
+    ) );
+    group.bench_function ( "DW28::find_listed_ric", |b| b.iter ( ||
+        symbols.iter ( )
+            .filter_map ( |symbol| DW28::find_listed_ric ( black_box ( &content ), symbol ) )
+            .count ( )
+    ) );
+    group.finish ( );
+}
+
+criterion_group ! ( benches, bench_parse, bench_listed_ric );
+criterion_main ! ( benches );
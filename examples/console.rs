@@ -18,7 +18,7 @@ async fn main ( ) {
     print ! ("Type in DW symbol: ");
     stdin().read_line(&mut input).unwrap();
     
-    let dw_info = dw::DWInfo::from_str(input.as_str().trim()).unwrap ( );
+    let dw_info = input.trim().parse::<dw::DWInfo>().unwrap ( );
     println ! ( "{:?}", dw_info );
     let out = dw::DWInfo::get_underlying_dw_price_table ( &dw_info )
         .await;
@@ -20,7 +20,7 @@ pub async fn main() {
 		}
 	} else {
 		for symbol in symbols.iter() {
-			let dw_info = dw::DWInfo::from_str(&symbol).unwrap ( );
+			let dw_info = symbol.parse::<dw::DWInfo>().unwrap ( );
 			println ! ( "{:?}", dw_info );
 			let out = dw06::DW06::get_underlying_dw_price_table( &dw_info )
 				.await;
@@ -17,7 +17,7 @@ async fn main ( ) {
     }
 
     for symbol in symbols.iter() {
-        let dw_info = dw::DWInfo::from_str(&symbol).unwrap ( );
+        let dw_info = symbol.parse::<dw::DWInfo>().unwrap ( );
         println ! ( "{:?}", dw_info );
         let out = dw13::DW13::get_underlying_dw_price_table( &dw_info )
             .await;
@@ -0,0 +1,287 @@
+//! # Backtest over recorded price tables
+//!
+//! Replays recorded [PriceTable] snapshots of a DW with a series of the underlying price, and
+//! fills the orders of a [Strategy] at the quoted DW bid/ask of the latest snapshot, on the SET
+//! tick and board lot rules, with fees.
+
+use chrono::NaiveDateTime;
+
+use crate::{
+    price::Price,
+    price_table::{
+        Match,
+        PriceTable,
+    },
+    ticks::{
+        Snap,
+        TickLadder,
+        DW,
+    },
+};
+
+/// Number of DWs in a board lot of SET.
+pub const BOARD_LOT: u64 = 100;
+
+/// Recorded price table at a point of time.
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    pub time: NaiveDateTime,
+    /// Price table, normalized to [crate::convention::QuoteConvention::NORMALIZED].
+    pub table: PriceTable,
+}
+
+/// Trading fees, charged on the value of each trade.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fees {
+    /// Commission rate, e.g. 0.0015 for 0.15%.
+    pub commission_rate: f64,
+    /// Minimum commission per trade.
+    pub minimum_commission: f64,
+    /// VAT rate on the commission, e.g. 0.07 for 7%.
+    pub vat_rate: f64,
+}
+
+impl Fees {
+    /// No fees.
+    pub const NONE: Fees = Fees {
+        commission_rate: 0.0,
+        minimum_commission: 0.0,
+        vat_rate: 0.0,
+    };
+
+    /// Returns the fees of a trade of given value.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - Value of the trade.
+    pub fn of ( &self, value: f64 ) -> f64 {
+        ( value * self.commission_rate ).max ( self.minimum_commission ) * ( 1.0 + self.vat_rate )
+    }
+}
+
+/// Side of an order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Buy,
+    Sell,
+}
+
+/// Order of a strategy, filled at the quoted ask for [Side::Buy] or bid for [Side::Sell].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Order {
+    pub side: Side,
+    /// Number of DWs, rounded down to [BOARD_LOT].
+    pub quantity: u64,
+}
+
+/// Strategy to be backtested.
+pub trait Strategy {
+    /// Returns the order at given underlying price, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `time` - Time of the underlying price.
+    /// * `underlying` - Underlying bid, on the tick ladder of the underlying.
+    /// * `position` - Number of DWs held.
+    fn on_underlying ( &mut self, time: NaiveDateTime, underlying: Price, position: u64 ) -> Option<Order>;
+}
+
+/// Buys the DW when the underlying reaches the entry level, and sells all when it reaches the exit level.
+///
+/// For a call, the entry is below the exit. For a put, the entry is above the exit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LevelRule {
+    pub entry: Price,
+    pub exit: Price,
+    pub quantity: u64,
+}
+
+impl Strategy for LevelRule {
+    fn on_underlying ( &mut self, _time: NaiveDateTime, underlying: Price, position: u64 ) -> Option<Order> {
+        let is_rising = self.entry <= self.exit;
+        let reached = |level: Price| if is_rising == ( level == self.entry ) { underlying <= level } else { underlying >= level };
+        if position == 0 && reached ( self.entry ) {
+            Some ( Order { side: Side::Buy, quantity: self.quantity } )
+        } else if position > 0 && reached ( self.exit ) {
+            Some ( Order { side: Side::Sell, quantity: position } )
+        } else {
+            None
+        }
+    }
+}
+
+/// Filled order.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Trade {
+    pub time: NaiveDateTime,
+    pub underlying: Price,
+    pub side: Side,
+    pub quantity: u64,
+    pub price: Price,
+    pub fees: f64,
+}
+
+/// Result of a backtest.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BacktestResult {
+    pub trades: Vec<Trade>,
+    /// Number of DWs held at the end.
+    pub position: u64,
+    /// Cash flow of the trades, after fees.
+    pub cash: f64,
+    /// Total fees.
+    pub fees: f64,
+    /// Value of the position at the last quoted bid.
+    pub market_value: f64,
+}
+
+impl BacktestResult {
+    /// Returns the P&L, including the position marked at the last quoted bid.
+    pub fn pnl ( &self ) -> f64 {
+        self.cash + self.market_value
+    }
+}
+
+/// Returns the result of the strategy over given snapshots and underlying prices.
+///
+/// Each underlying price is snapped down to the tick ladder, and quoted by the latest snapshot at
+/// or before its time, on the column of its date. Orders are not filled if not quoted. If the ask
+/// is not published, it is assumed one DW tick above the bid.
+///
+/// # Arguments
+///
+/// * `snapshots` - Recorded price tables of one DW, sorted by time.
+/// * `underlying` - Pairs of time and underlying bid, sorted by time.
+/// * `ladder` - Tick ladder of the underlying.
+/// * `fees` - Trading fees.
+/// * `strategy` - Strategy to be backtested.
+pub fn run<S: Strategy> ( snapshots: &[Snapshot], underlying: &[( NaiveDateTime, Price )], ladder: &TickLadder, fees: &Fees, strategy: &mut S ) -> BacktestResult {
+    let mut result = BacktestResult::default ( );
+    let mut last_bid = None;
+
+    for &( time, price ) in underlying {
+        let price = ladder.snap ( price, Snap::Down );
+        let snapshot = match snapshots.iter ( ).rev ( ).find ( |s| s.time <= time ) {
+            Some ( snapshot ) => snapshot,
+            None => continue,
+        };
+        let quote = match snapshot.table.get_quote ( time.date ( ), price, Match::Floor ) {
+            Some ( quote ) => quote,
+            None => continue,
+        };
+        last_bid = Some ( quote.bid );
+
+        let order = match strategy.on_underlying ( time, price, result.position ) {
+            Some ( order ) => order,
+            None => continue,
+        };
+        let quantity = match order.side {
+            Side::Buy => order.quantity,
+            Side::Sell => order.quantity.min ( result.position ),
+        } / BOARD_LOT * BOARD_LOT;
+        let fill = match order.side {
+            Side::Buy => quote.ask.or_else ( || DW.step ( quote.bid, 1 ) ),
+            Side::Sell => Some ( quote.bid ).filter ( |bid| ! bid.is_zero ( ) ),
+        };
+        let fill = match fill {
+            Some ( fill ) if quantity > 0 => fill,
+            _ => continue,
+        };
+
+        let value = fill.to_f64 ( ) * quantity as f64;
+        let trade_fees = fees.of ( value );
+        match order.side {
+            Side::Buy => {
+                result.position += quantity;
+                result.cash -= value + trade_fees;
+            },
+            Side::Sell => {
+                result.position -= quantity;
+                result.cash += value - trade_fees;
+            },
+        }
+        result.fees += trade_fees;
+        result.trades.push ( Trade {
+            time,
+            underlying: price,
+            side: order.side,
+            quantity,
+            price: fill,
+            fees: trade_fees,
+        } );
+    }
+
+    result.market_value = last_bid.map ( |bid| bid.to_f64 ( ) * result.position as f64 ).unwrap_or ( 0.0 );
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        testing::{
+            date,
+            ladder_column,
+            p,
+            table,
+        },
+        ticks::SET50_INDEX,
+    };
+
+    fn time ( day: u32, hour: u32 ) -> NaiveDateTime {
+        date ( day ).and_hms_opt ( hour, 0, 0 ).unwrap ( )
+    }
+
+    /// Returns the snapshot with the DW bid 0.50 at 900, rising 0.01 per index point.
+    fn snapshot ( day: u32 ) -> Snapshot {
+        Snapshot {
+            time: time ( day, 9 ),
+            table: table ( "S5028C2103A", vec ! [ ladder_column ( day, 89000, 100, 20, |i| (
+                Price::new ( 40 + i, 2 ),
+                if i % 2 == 0 { Some ( Price::new ( 41 + i, 2 ) ) } else { None },
+            ) ) ] ),
+        }
+    }
+
+    #[test]
+    fn test_run ( ) {
+        let snapshots = [ snapshot ( 23 ), snapshot ( 24 ) ];
+        let underlying = [
+            // before the first snapshot
+            ( time ( 23, 8 ), p ( "895" ) ),
+            ( time ( 23, 10 ), p ( "901.456" ) ),
+            ( time ( 23, 11 ), p ( "898.20" ) ),
+            ( time ( 23, 12 ), p ( "899" ) ),
+            ( time ( 24, 10 ), p ( "905.30" ) ),
+            ( time ( 24, 11 ), p ( "906" ) ),
+        ];
+        let fees = Fees { commission_rate: 0.01, minimum_commission: 0.0, vat_rate: 0.07 };
+        let mut rule = LevelRule { entry: p ( "898.50" ), exit: p ( "905" ), quantity: 1050 };
+
+        let result = run ( &snapshots, &underlying, &SET50_INDEX, &fees, &mut rule );
+
+        // bought 1000 at 0.49 as the ask of 898, sold 1000 at the bid 0.55 of 905
+        assert_eq ! ( result.trades.len ( ), 2 );
+        assert_eq ! ( ( result.trades [ 0 ].side, result.trades [ 0 ].quantity, result.trades [ 0 ].price ), ( Side::Buy, 1000, p ( "0.49" ) ) );
+        assert_eq ! ( result.trades [ 0 ].underlying, p ( "898.20" ) );
+        assert_eq ! ( ( result.trades [ 1 ].side, result.trades [ 1 ].price ), ( Side::Sell, p ( "0.55" ) ) );
+        assert_eq ! ( result.position, 0 );
+
+        let expected_fees = ( 490.0 + 550.0 ) * 0.01 * 1.07;
+        assert ! ( ( result.fees - expected_fees ).abs ( ) < 1e-9 );
+        assert ! ( ( result.pnl ( ) - ( 60.0 - expected_fees ) ).abs ( ) < 1e-9 );
+    }
+
+    #[test]
+    fn test_run_assumed_ask ( ) {
+        // the ask is not published at 899, so it is one DW tick above the bid
+        let mut rule = LevelRule { entry: p ( "899" ), exit: p ( "910" ), quantity: 100 };
+        let result = run ( &[ snapshot ( 23 ) ], &[ ( time ( 23, 10 ), p ( "899.99" ) ) ], &SET50_INDEX, &Fees::NONE, &mut rule );
+        assert_eq ! ( result.trades.len ( ), 0 );
+
+        let result = run ( &[ snapshot ( 23 ) ], &[ ( time ( 23, 10 ), p ( "899.00" ) ) ], &SET50_INDEX, &Fees::NONE, &mut rule );
+        assert_eq ! ( result.trades [ 0 ].price, p ( "0.50" ) );
+        assert_eq ! ( result.position, 100 );
+        assert ! ( ( result.market_value - 49.0 ).abs ( ) < 1e-9 );
+    }
+}
@@ -0,0 +1,284 @@
+//! # Black-Scholes DW fair value and implied volatility
+//!
+//! The underlying option is priced by Black-Scholes with continuous dividend yield, then divided
+//! by the conversion ratio to get the DW price.
+
+use chrono::{
+    Datelike,
+    NaiveDate,
+};
+
+use crate::{
+    instrument::dw::{
+        DWInfo,
+        DWSide,
+    },
+    price::Price,
+    price_table::PriceTable,
+};
+
+/// Lowest volatility searched by [implied_volatility].
+const MIN_VOLATILITY: f64 = 1e-4;
+/// Highest volatility searched by [implied_volatility].
+const MAX_VOLATILITY: f64 = 5.0;
+/// Tolerance of the price in [implied_volatility].
+const PRICE_TOLERANCE: f64 = 1e-10;
+
+/// Contract terms of a DW.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DWTerms {
+    pub side: DWSide,
+    /// Exercise price, in the underlying price unit.
+    pub strike: f64,
+    /// Number of DWs per one unit of the underlying.
+    pub conversion_ratio: f64,
+    /// The last date of the time value, usually the last trading date.
+    pub expiry: NaiveDate,
+}
+
+/// Market parameters of the underlying, as annual continuous rates, e.g. 0.015 for 1.5%.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct MarketParams {
+    pub rate: f64,
+    pub dividend_yield: f64,
+}
+
+/// Implied volatility of one row of a price table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImpliedVolatility {
+    pub date: NaiveDate,
+    pub underlying: Price,
+    pub dw_price: Price,
+    pub volatility: f64,
+}
+
+impl DWTerms {
+    /// Returns the terms of given DW, with the expiry at the end of the month of [DWInfo::expiry].
+    ///
+    /// Returns None if the side or the expiry month is unknown.
+    ///
+    /// # Arguments
+    ///
+    /// * `dw_info` - DW info.
+    /// * `strike` - Exercise price.
+    /// * `conversion_ratio` - Number of DWs per one unit of the underlying.
+    pub fn from_dw_info ( dw_info: &DWInfo, strike: f64, conversion_ratio: f64 ) -> Option<DWTerms> {
+        if dw_info.side == DWSide::Unknown {
+            return None;
+        }
+        Some ( DWTerms {
+            side: dw_info.side.clone ( ),
+            strike,
+            conversion_ratio,
+            expiry: dw_info.expiry ( )?.month_end ( ),
+        } )
+    }
+
+    /// Returns the years from given date to the expiry, by actual/365. 0 if expired.
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - Valuation date.
+    pub fn time_to_expiry ( &self, date: NaiveDate ) -> f64 {
+        ( self.expiry.num_days_from_ce ( ) - date.num_days_from_ce ( ) ).max ( 0 ) as f64 / 365.0
+    }
+
+    /// Returns the fair value of one DW.
+    ///
+    /// # Arguments
+    ///
+    /// * `spot` - Underlying price.
+    /// * `date` - Valuation date.
+    /// * `volatility` - Annual volatility, e.g. 0.3 for 30%.
+    /// * `params` - Market parameters.
+    pub fn fair_value ( &self, spot: f64, date: NaiveDate, volatility: f64, params: &MarketParams ) -> f64 {
+        black_scholes ( &self.side, spot, self.strike, self.time_to_expiry ( date ), volatility, params ) / self.conversion_ratio
+    }
+
+    /// Returns the volatility implied by the DW price, or None if the price is out of the model's range.
+    ///
+    /// # Arguments
+    ///
+    /// * `spot` - Underlying price.
+    /// * `date` - Valuation date.
+    /// * `dw_price` - DW price.
+    /// * `params` - Market parameters.
+    pub fn implied_volatility ( &self, spot: f64, date: NaiveDate, dw_price: f64, params: &MarketParams ) -> Option<f64> {
+        implied_volatility ( &self.side, spot, self.strike, self.time_to_expiry ( date ), dw_price * self.conversion_ratio, params )
+    }
+
+    /// Returns the volatility implied by the DW bid of each row of the table, skipping the rows
+    /// without a bid or out of the model's range.
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - Price table of the DW.
+    /// * `params` - Market parameters.
+    pub fn implied_volatilities ( &self, table: &PriceTable, params: &MarketParams ) -> Vec<ImpliedVolatility> {
+        table.columns.iter ( )
+            .flat_map ( |column| column.quotes.iter ( ).map ( move |quote| ( column.date, quote ) ) )
+            .filter ( |(_, quote)| ! quote.bid.is_zero ( ) )
+            .filter_map ( |(date, quote)| {
+                self.implied_volatility ( quote.underlying_bid.to_f64 ( ), date, quote.bid.to_f64 ( ), params )
+                    .map ( |volatility| ImpliedVolatility {
+                        date,
+                        underlying: quote.underlying_bid,
+                        dw_price: quote.bid,
+                        volatility,
+                    } )
+            } )
+            .collect ( )
+    }
+}
+
+/// Returns the standard normal cumulative distribution.
+pub fn norm_cdf ( x: f64 ) -> f64 {
+    0.5 * erfc ( -x / std::f64::consts::SQRT_2 )
+}
+
+/// Returns the standard normal probability density.
+pub fn norm_pdf ( x: f64 ) -> f64 {
+    ( -0.5 * x * x ).exp ( ) / ( 2.0 * std::f64::consts::PI ).sqrt ( )
+}
+
+/// Returns the complementary error function, with the fractional error below 1.2e-7.
+fn erfc ( x: f64 ) -> f64 {
+    let z = x.abs ( );
+    let t = 1.0 / ( 1.0 + 0.5 * z );
+    let r = t * ( -z * z - 1.265_512_23 + t * ( 1.000_023_68 + t * ( 0.374_091_96 + t * ( 0.096_784_18
+        + t * ( -0.186_288_06 + t * ( 0.278_868_07 + t * ( -1.135_203_98 + t * ( 1.488_515_87
+        + t * ( -0.822_152_23 + t * 0.170_872_77 ) ) ) ) ) ) ) ) ).exp ( );
+    if x >= 0.0 {
+        r
+    } else {
+        2.0 - r
+    }
+}
+
+/// Returns the Black-Scholes price of one unit of the underlying option.
+///
+/// At or after expiry, or with zero volatility, the discounted intrinsic value is returned.
+///
+/// # Arguments
+///
+/// * `side` - Call or put. [DWSide::Unknown] is priced as 0.
+/// * `spot` - Underlying price.
+/// * `strike` - Exercise price.
+/// * `time` - Years to expiry.
+/// * `volatility` - Annual volatility.
+/// * `params` - Market parameters.
+pub fn black_scholes ( side: &DWSide, spot: f64, strike: f64, time: f64, volatility: f64, params: &MarketParams ) -> f64 {
+    let forward = spot * ( -params.dividend_yield * time ).exp ( );
+    let discounted_strike = strike * ( -params.rate * time ).exp ( );
+    let std_dev = volatility * time.sqrt ( );
+
+    if std_dev <= 0.0 {
+        return match side {
+            DWSide::C => ( forward - discounted_strike ).max ( 0.0 ),
+            DWSide::P => ( discounted_strike - forward ).max ( 0.0 ),
+            DWSide::Unknown => 0.0,
+        };
+    }
+
+    let d1 = ( ( spot / strike ).ln ( ) + ( params.rate - params.dividend_yield + 0.5 * volatility * volatility ) * time ) / std_dev;
+    let d2 = d1 - std_dev;
+    match side {
+        DWSide::C => forward * norm_cdf ( d1 ) - discounted_strike * norm_cdf ( d2 ),
+        DWSide::P => discounted_strike * norm_cdf ( -d2 ) - forward * norm_cdf ( -d1 ),
+        DWSide::Unknown => 0.0,
+    }
+}
+
+/// Returns the volatility where the Black-Scholes price equals given price, by bisection, or
+/// None if the price is out of the range of the volatility from 0.01% to 500%.
+///
+/// # Arguments
+///
+/// * `side` - Call or put.
+/// * `spot` - Underlying price.
+/// * `strike` - Exercise price.
+/// * `time` - Years to expiry.
+/// * `price` - Price of one unit of the underlying option, i.e. DW price times the conversion ratio.
+/// * `params` - Market parameters.
+pub fn implied_volatility ( side: &DWSide, spot: f64, strike: f64, time: f64, price: f64, params: &MarketParams ) -> Option<f64> {
+    if time <= 0.0 || *side == DWSide::Unknown {
+        return None;
+    }
+    let ( mut low, mut high ) = ( MIN_VOLATILITY, MAX_VOLATILITY );
+    if price < black_scholes ( side, spot, strike, time, low, params ) - PRICE_TOLERANCE
+        || price > black_scholes ( side, spot, strike, time, high, params ) + PRICE_TOLERANCE {
+        return None;
+    }
+    for _ in 0..200 {
+        let mid = 0.5 * ( low + high );
+        let diff = black_scholes ( side, spot, strike, time, mid, params ) - price;
+        if diff.abs ( ) < PRICE_TOLERANCE {
+            return Some ( mid );
+        }
+        if diff > 0.0 {
+            high = mid;
+        } else {
+            low = mid;
+        }
+    }
+    Some ( 0.5 * ( low + high ) )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn assert_close ( actual: f64, expected: f64, tolerance: f64 ) {
+        assert ! ( ( actual - expected ).abs ( ) < tolerance, "actual={} expected={}", actual, expected );
+    }
+
+    #[test]
+    fn test_norm_cdf ( ) {
+        assert_close ( norm_cdf ( 0.0 ), 0.5, 1e-7 );
+        assert_close ( norm_cdf ( 1.0 ), 0.841_344_746, 1e-7 );
+        assert_close ( norm_cdf ( -1.96 ), 0.024_997_895, 1e-7 );
+    }
+
+    #[test]
+    fn test_black_scholes ( ) {
+        // Hull, Options, Futures, and Other Derivatives: S=42, K=40, r=10%, sigma=20%, T=0.5
+        let params = MarketParams { rate: 0.1, dividend_yield: 0.0 };
+        assert_close ( black_scholes ( &DWSide::C, 42.0, 40.0, 0.5, 0.2, &params ), 4.76, 5e-3 );
+        assert_close ( black_scholes ( &DWSide::P, 42.0, 40.0, 0.5, 0.2, &params ), 0.81, 5e-3 );
+
+        // put-call parity with dividend yield
+        let params = MarketParams { rate: 0.02, dividend_yield: 0.015 };
+        let call = black_scholes ( &DWSide::C, 900.0, 910.0, 0.1, 0.3, &params );
+        let put = black_scholes ( &DWSide::P, 900.0, 910.0, 0.1, 0.3, &params );
+        assert_close ( call - put, 900.0 * ( -0.0015f64 ).exp ( ) - 910.0 * ( -0.002f64 ).exp ( ), 1e-6 );
+
+        // expired
+        assert_close ( black_scholes ( &DWSide::C, 920.0, 910.0, 0.0, 0.3, &params ), 10.0, 1e-9 );
+    }
+
+    #[test]
+    fn test_implied_volatility ( ) {
+        let params = MarketParams { rate: 0.01, dividend_yield: 0.0 };
+        for volatility in [ 0.05, 0.2, 0.6, 1.5 ] {
+            let price = black_scholes ( &DWSide::P, 3673.5, 3320.0, 0.25, volatility, &params );
+            assert_close ( implied_volatility ( &DWSide::P, 3673.5, 3320.0, 0.25, price, &params ).unwrap ( ), volatility, 1e-6 );
+        }
+        // below the intrinsic value
+        assert_eq ! ( implied_volatility ( &DWSide::C, 920.0, 900.0, 0.1, 1.0, &params ), None );
+        assert_eq ! ( implied_volatility ( &DWSide::C, 920.0, 900.0, 0.0, 25.0, &params ), None );
+    }
+
+    #[test]
+    fn test_dw_terms ( ) {
+        let dw_info = DWInfo::from_str ( "S5028C2012D" ).unwrap ( );
+        let terms = DWTerms::from_dw_info ( &dw_info, 910.0, 20.0 ).unwrap ( );
+        assert_eq ! ( terms.expiry, NaiveDate::from_ymd_opt ( 2020, 12, 31 ).unwrap ( ) );
+
+        let date = NaiveDate::from_ymd_opt ( 2020, 12, 23 ).unwrap ( );
+        let params = MarketParams::default ( );
+        let dw_price = terms.fair_value ( 900.5, date, 0.326, &params );
+        assert_close ( terms.implied_volatility ( 900.5, date, dw_price, &params ).unwrap ( ), 0.326, 1e-6 );
+        assert_eq ! ( terms.time_to_expiry ( NaiveDate::from_ymd_opt ( 2021, 1, 4 ).unwrap ( ) ), 0.0 );
+    }
+}
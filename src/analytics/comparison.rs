@@ -0,0 +1,212 @@
+//! # Cross-issuer comparison
+//!
+//! Compares DWs of several issuers on the same underlying at one underlying price. The metrics are
+//! normalized by the conversion ratio of each DW, so they are per one unit of the underlying,
+//! whatever the DW price level.
+
+use std::{
+    cmp::Ordering,
+    fmt,
+};
+
+use chrono::NaiveDate;
+
+use crate::{
+    analytics::{
+        greeks::get_table_greeks,
+        theta::get_theta,
+    },
+    price::Price,
+    price_table::{
+        Match,
+        PriceTable,
+    },
+    ticks::{
+        TickLadder,
+        DW,
+    },
+};
+
+/// DW to be compared.
+#[derive(Debug, Clone, Copy)]
+pub struct Candidate<'a> {
+    /// Price table, normalized to [crate::convention::QuoteConvention::NORMALIZED].
+    pub table: &'a PriceTable,
+    /// Number of DWs per one unit of the underlying.
+    pub conversion_ratio: f64,
+}
+
+/// Metrics of one DW at the compared underlying price.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub dw_symbol: Box<str>,
+    pub bid: Price,
+    pub ask: Option<Price>,
+    /// Delta of the underlying option, e.g. 0.417 for 41.7%.
+    pub delta: f64,
+    /// Cost of one unit of delta in the underlying, i.e. DW ask, or bid if not quoted, times the
+    /// conversion ratio, over the delta.
+    pub cost_per_delta: Option<f64>,
+    /// DW ticks between bid and ask. None if the ask is not quoted.
+    pub spread_ticks: Option<i64>,
+    /// Underlying ticks for the DW bid to move one DW tick.
+    pub sensitivity: Option<f64>,
+    /// Change of the DW bid per calendar day to the next column, times the conversion ratio.
+    pub theta_per_day: Option<f64>,
+}
+
+/// Metric to rank the comparisons by, the best first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RankBy {
+    /// Lowest cost per unit delta first.
+    CostPerDelta,
+    /// Narrowest spread first.
+    Spread,
+    /// Fewest underlying ticks per DW tick first.
+    Sensitivity,
+    /// Slowest time decay first.
+    TimeDecay,
+}
+
+/// Returns the comparisons of given DWs on given date at given underlying price, in the order of
+/// the candidates. DWs not quoted at the underlying price are skipped.
+///
+/// # Arguments
+///
+/// * `candidates` - DWs on the same underlying.
+/// * `date` - Date of the columns.
+/// * `underlying` - Underlying bid.
+/// * `ladder` - Tick ladder of the underlying.
+/// * `window_ticks` - Underlying ticks between the points of the differences. See [crate::analytics::greeks::get_greeks].
+pub fn compare ( candidates: &[Candidate], date: NaiveDate, underlying: Price, ladder: &TickLadder, window_ticks: i64 ) -> Vec<Comparison> {
+    candidates.iter ( )
+        .filter_map ( |candidate| {
+            let quote = candidate.table.get_column ( date )?.get_quote ( underlying, Match::Floor )?;
+            let greeks = get_table_greeks ( candidate.table, date, underlying, ladder, window_ticks )?;
+            let delta = greeks.delta ( candidate.conversion_ratio );
+
+            let cost_per_delta = if delta == 0.0 {
+                None
+            } else {
+                Some ( quote.ask.unwrap_or ( quote.bid ).to_f64 ( ) * candidate.conversion_ratio / delta.abs ( ) )
+            };
+
+            let theta_per_day = get_theta ( candidate.table, underlying ).into_iter ( )
+                .find ( |theta| theta.from == date )
+                .map ( |theta| theta.per_day ( ) * candidate.conversion_ratio );
+
+            Some ( Comparison {
+                dw_symbol: candidate.table.dw_symbol.clone ( ),
+                bid: quote.bid,
+                ask: quote.ask,
+                delta,
+                cost_per_delta,
+                spread_ticks: quote.ask.map ( |ask| DW.ticks_between ( quote.bid, ask ) ),
+                sensitivity: greeks.sensitivity,
+                theta_per_day,
+            } )
+        } )
+        .collect ( )
+}
+
+/// Sorts the comparisons by given metric, the best first. Comparisons without the metric are last.
+///
+/// # Arguments
+///
+/// * `comparisons` - Comparisons to be sorted.
+/// * `rank_by` - Metric to rank by.
+pub fn rank ( comparisons: &mut [Comparison], rank_by: RankBy ) {
+    let key = |c: &Comparison| match rank_by {
+        RankBy::CostPerDelta => c.cost_per_delta,
+        RankBy::Spread => c.spread_ticks.map ( |ticks| ticks as f64 ),
+        RankBy::Sensitivity => c.sensitivity,
+        RankBy::TimeDecay => c.theta_per_day.map ( f64::abs ),
+    };
+    comparisons.sort_by ( |a, b| match ( key ( a ), key ( b ) ) {
+        ( Some ( a ), Some ( b ) ) => a.partial_cmp ( &b ).unwrap_or ( Ordering::Equal ),
+        ( Some ( _ ), None ) => Ordering::Less,
+        ( None, Some ( _ ) ) => Ordering::Greater,
+        ( None, None ) => Ordering::Equal,
+    } );
+}
+
+/// Comparisons formatted as a text table, one DW per line.
+pub struct ComparisonTable<'a> ( pub &'a [Comparison] );
+
+impl fmt::Display for ComparisonTable<'_> {
+    fn fmt ( &self, f: &mut fmt::Formatter ) -> fmt::Result {
+        let optional = |value: Option<f64>, precision: usize| value
+            .map ( |v| format ! ( "{:.*}", precision, v ) )
+            .unwrap_or_else ( || "-".to_owned ( ) );
+
+        writeln ! ( f, "{:<14} {:>7} {:>7} {:>7} {:>10} {:>7} {:>7} {:>9}",
+            "DW", "Bid", "Ask", "Delta", "Cost/Delta", "Spread", "Sens.", "Theta/Day" )?;
+        for c in self.0 {
+            writeln ! ( f, "{:<14} {:>7} {:>7} {:>7.3} {:>10} {:>7} {:>7} {:>9}",
+                c.dw_symbol,
+                c.bid.to_string ( ),
+                c.ask.map ( |ask| ask.to_string ( ) ).unwrap_or_else ( || "-".to_owned ( ) ),
+                c.delta,
+                optional ( c.cost_per_delta, 2 ),
+                c.spread_ticks.map ( |ticks| ticks.to_string ( ) ).unwrap_or_else ( || "-".to_owned ( ) ),
+                optional ( c.sensitivity, 2 ),
+                optional ( c.theta_per_day, 3 ),
+            )?;
+        }
+        Ok ( ( ) )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        testing::{
+            date,
+            ladder_column,
+            p,
+        },
+        ticks::SET50_INDEX,
+    };
+
+    /// Returns the table of bid = base + step * (underlying - 900), with given spread in DW ticks.
+    fn table ( symbol: &str, base: f64, step: f64, decay: f64, spread: Option<i64> ) -> PriceTable {
+        let column = |day: u32, base: f64| ladder_column ( day, 90000, 100, 20, |i| {
+            let bid = Price::new ( ( ( base + step * i as f64 ) * 100.0 ).round ( ) as i64, 2 );
+            ( bid, spread.and_then ( |ticks| DW.step ( bid, ticks ) ) )
+        } );
+        crate::testing::table ( symbol, vec ! [ column ( 23, base ), column ( 24, base - decay ) ] )
+    }
+
+    #[test]
+    fn test_compare ( ) {
+        // same delta on different ratios, with a narrower spread but faster decay on DW28
+        let dw13 = table ( "S5013C2103A", 1.00, 0.02, 0.02, Some ( 2 ) );
+        let dw28 = table ( "S5028C2103A", 0.50, 0.01, 0.02, Some ( 1 ) );
+        let candidates = [
+            Candidate { table: &dw13, conversion_ratio: 20.0 },
+            Candidate { table: &dw28, conversion_ratio: 40.0 },
+        ];
+
+        let mut comparisons = compare ( &candidates, date ( 23 ), p ( "901.00" ), &SET50_INDEX, 50 );
+        assert_eq ! ( comparisons.len ( ), 2 );
+        assert ! ( ( comparisons [ 0 ].delta - 0.4 ).abs ( ) < 1e-9 );
+        assert ! ( ( comparisons [ 1 ].delta - 0.4 ).abs ( ) < 1e-9 );
+        assert_eq ! ( comparisons [ 0 ].spread_ticks, Some ( 2 ) );
+        assert ! ( ( comparisons [ 0 ].theta_per_day.unwrap ( ) + 0.4 ).abs ( ) < 1e-9 );
+        assert ! ( ( comparisons [ 1 ].theta_per_day.unwrap ( ) + 0.8 ).abs ( ) < 1e-9 );
+
+        rank ( &mut comparisons, RankBy::Spread );
+        assert_eq ! ( comparisons [ 0 ].dw_symbol.as_ref ( ), "S5028C2103A" );
+
+        rank ( &mut comparisons, RankBy::TimeDecay );
+        assert_eq ! ( comparisons [ 0 ].dw_symbol.as_ref ( ), "S5013C2103A" );
+
+        let text = ComparisonTable ( &comparisons ).to_string ( );
+        assert_eq ! ( text.lines ( ).count ( ), 3 );
+        assert ! ( text.lines ( ).nth ( 1 ).unwrap ( ).starts_with ( "S5013C2103A" ) );
+
+        // out of the tables
+        assert ! ( compare ( &candidates, date ( 23 ), p ( "800" ), &SET50_INDEX, 50 ).is_empty ( ) );
+    }
+}
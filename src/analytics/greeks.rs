@@ -0,0 +1,173 @@
+//! # Greeks from price tables
+//!
+//! Delta, gamma, sensitivity and effective gearing by finite differences on the DW bid of a
+//! table column, so the metrics are comparable across issuers, whatever they publish.
+
+use chrono::NaiveDate;
+
+use crate::{
+    price::Price,
+    price_table::{
+        Match,
+        PriceColumn,
+        PriceTable,
+    },
+    ticks::{
+        TickLadder,
+        DW,
+    },
+};
+
+/// Greeks of a DW at one underlying price.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Greeks {
+    pub underlying: Price,
+    /// DW bid at the underlying price, interpolated.
+    pub dw_price: f64,
+    /// Change of the DW bid per one unit of the underlying price.
+    pub dw_delta: f64,
+    /// Change of [Greeks::dw_delta] per one unit of the underlying price.
+    pub gamma: f64,
+    /// Underlying ticks for the DW bid to move one DW tick. None if the DW bid does not move.
+    pub sensitivity: Option<f64>,
+    /// Percentage change of the DW per 1% change of the underlying. None if the DW bid is 0.
+    pub effective_gearing: Option<f64>,
+}
+
+impl Greeks {
+    /// Returns the delta of the underlying option, e.g. 0.417 for the issuer's 41.7%.
+    ///
+    /// # Arguments
+    ///
+    /// * `conversion_ratio` - Number of DWs per one unit of the underlying.
+    pub fn delta ( &self, conversion_ratio: f64 ) -> f64 {
+        self.dw_delta * conversion_ratio
+    }
+}
+
+/// Returns the greeks of the column at given underlying price, by central differences over
+/// [window_ticks] underlying ticks on each side, or one-sided differences at the edge of the table.
+///
+/// The tables are step functions of the underlying, so the window should span a few DW ticks,
+/// e.g. 100 ticks of SET50 index.
+///
+/// Returns None if the underlying price is out of the table, or the table has no other row
+/// within the window.
+///
+/// # Arguments
+///
+/// * `column` - Column of the table.
+/// * `underlying` - Underlying bid.
+/// * `ladder` - Tick ladder of the underlying.
+/// * `window_ticks` - Underlying ticks between the points of the differences. At least 1.
+pub fn get_greeks ( column: &PriceColumn, underlying: Price, ladder: &TickLadder, window_ticks: i64 ) -> Option<Greeks> {
+    let window_ticks = window_ticks.max ( 1 );
+    let dw_price = column.interpolate_bid ( underlying )?;
+
+    let value_at = |price: Option<Price>| price.and_then ( |price| column.interpolate_bid ( price ).map ( |bid| ( price.to_f64 ( ), bid ) ) );
+    let lower = value_at ( ladder.step ( underlying, -window_ticks ) );
+    let upper = value_at ( ladder.step ( underlying, window_ticks ) );
+    let center = ( underlying.to_f64 ( ), dw_price );
+
+    let ( dw_delta, gamma ) = match ( lower, upper ) {
+        ( Some ( lower ), Some ( upper ) ) => (
+            ( upper.1 - lower.1 ) / ( upper.0 - lower.0 ),
+            ( ( upper.1 - center.1 ) / ( upper.0 - center.0 ) - ( center.1 - lower.1 ) / ( center.0 - lower.0 ) )
+                / ( 0.5 * ( upper.0 - lower.0 ) ),
+        ),
+        ( Some ( lower ), None ) => ( ( center.1 - lower.1 ) / ( center.0 - lower.0 ), 0.0 ),
+        ( None, Some ( upper ) ) => ( ( upper.1 - center.1 ) / ( upper.0 - center.0 ), 0.0 ),
+        ( None, None ) => return None,
+    };
+
+    let sensitivity = if dw_delta == 0.0 {
+        None
+    } else {
+        column.get_quote ( underlying, Match::Floor )
+            .map ( |quote| DW.tick_size ( quote.bid ).to_f64 ( ) / dw_delta.abs ( ) / ladder.tick_size ( underlying ).to_f64 ( ) )
+    };
+
+    let effective_gearing = if dw_price > 0.0 {
+        Some ( dw_delta * underlying.to_f64 ( ) / dw_price )
+    } else {
+        None
+    };
+
+    Some ( Greeks {
+        underlying,
+        dw_price,
+        dw_delta,
+        gamma,
+        sensitivity,
+        effective_gearing,
+    } )
+}
+
+/// Returns the greeks of the table on given date. See [get_greeks].
+///
+/// # Arguments
+///
+/// * `table` - Price table, normalized to [crate::convention::QuoteConvention::NORMALIZED].
+/// * `date` - Date of the column.
+/// * `underlying` - Underlying bid.
+/// * `ladder` - Tick ladder of the underlying.
+/// * `window_ticks` - Underlying ticks between the points of the differences.
+pub fn get_table_greeks ( table: &PriceTable, date: NaiveDate, underlying: Price, ladder: &TickLadder, window_ticks: i64 ) -> Option<Greeks> {
+    get_greeks ( table.get_column ( date )?, underlying, ladder, window_ticks )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        testing::p,
+        ticks::SET_EQUITY,
+    };
+
+    fn column ( rows: &[(&str, &str)] ) -> PriceColumn {
+        crate::testing::column ( 23, rows )
+    }
+
+    fn assert_close ( actual: f64, expected: f64 ) {
+        assert ! ( ( actual - expected ).abs ( ) < 1e-9, "actual={} expected={}", actual, expected );
+    }
+
+    #[test]
+    fn test_get_greeks_linear ( ) {
+        // DW bid rises 0.01 per 0.50 of the underlying
+        let column = column ( &[ ( "100.00", "0.50" ), ( "100.50", "0.51" ), ( "101.00", "0.52" ), ( "101.50", "0.53" ) ] );
+        let greeks = get_greeks ( &column, p ( "100.50" ), &SET_EQUITY, 1 ).unwrap ( );
+
+        assert_close ( greeks.dw_price, 0.51 );
+        assert_close ( greeks.dw_delta, 0.02 );
+        assert_close ( greeks.gamma, 0.0 );
+        assert_close ( greeks.delta ( 10.0 ), 0.2 );
+        assert_close ( greeks.sensitivity.unwrap ( ), 1.0 );
+        assert_close ( greeks.effective_gearing.unwrap ( ), 0.02 * 100.5 / 0.51 );
+
+        // one-sided at the edge
+        let greeks = get_greeks ( &column, p ( "101.50" ), &SET_EQUITY, 1 ).unwrap ( );
+        assert_close ( greeks.dw_delta, 0.02 );
+
+        assert_eq ! ( get_greeks ( &column, p ( "99" ), &SET_EQUITY, 1 ), None );
+    }
+
+    #[test]
+    fn test_get_greeks_convex ( ) {
+        let column = column ( &[ ( "100", "0.10" ), ( "101", "0.12" ), ( "102", "0.16" ) ] );
+        let greeks = get_greeks ( &column, p ( "101" ), &SET_EQUITY, 2 ).unwrap ( );
+
+        assert_close ( greeks.dw_delta, 0.03 );
+        assert_close ( greeks.gamma, 0.02 );
+    }
+
+    #[test]
+    fn test_get_greeks_put ( ) {
+        let column = column ( &[ ( "100", "0.30" ), ( "101", "0.28" ), ( "102", "0.26" ) ] );
+        let greeks = get_greeks ( &column, p ( "101" ), &SET_EQUITY, 1 ).unwrap ( );
+
+        assert ! ( greeks.dw_delta < 0.0 );
+        assert ! ( greeks.effective_gearing.unwrap ( ) < 0.0 );
+        assert_close ( greeks.sensitivity.unwrap ( ), 1.0 );
+    }
+}
@@ -0,0 +1,11 @@
+//! # Analytics on DW price tables
+//!
+//! Pure Rust analytics, to be run offline on scraped or recorded [crate::price_table::PriceTable].
+
+pub mod black_scholes;
+pub mod greeks;
+pub mod theta;
+pub mod comparison;
+pub mod quality;
+pub mod backtest;
+pub mod portfolio;
@@ -0,0 +1,206 @@
+//! # Portfolio projection
+//!
+//! Projects the value and P&L of a book of DWs across scenarios of the underlying price and date,
+//! e.g. "if SET50 goes to 910 by Friday". Each DW is valued by its issuer's table where it is
+//! quoted, and by [crate::analytics::black_scholes] outside the table.
+
+use chrono::NaiveDate;
+
+use crate::{
+    analytics::black_scholes::{
+        DWTerms,
+        MarketParams,
+    },
+    instrument::dw::DWInfo,
+    price::Price,
+    price_table::PriceTable,
+};
+
+/// Black-Scholes model of a DW, to value it outside its table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Model {
+    pub terms: DWTerms,
+    /// Annual volatility, e.g. 0.3 for 30%.
+    pub volatility: f64,
+    pub params: MarketParams,
+}
+
+/// Position of one DW in the book.
+#[derive(Debug, Clone)]
+pub struct Position {
+    pub dw_info: DWInfo,
+    /// Number of DWs held.
+    pub quantity: u64,
+    /// Average cost per DW.
+    pub average_cost: f64,
+    /// Price table, normalized to [crate::convention::QuoteConvention::NORMALIZED].
+    pub table: PriceTable,
+    /// Model to value the DW outside the table. None to leave it unpriced there.
+    pub model: Option<Model>,
+}
+
+/// Source of a projected DW price.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    Table,
+    Model,
+}
+
+/// Scenario of the underlying price on a date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Scenario {
+    pub date: NaiveDate,
+    /// Underlying bid.
+    pub underlying: Price,
+}
+
+/// Projected value of one position in a scenario.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PositionValue {
+    pub dw_symbol: Box<str>,
+    pub dw_price: f64,
+    pub source: Source,
+    pub value: f64,
+    pub pnl: f64,
+}
+
+/// Projected values of the book in a scenario.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScenarioResult {
+    pub scenario: Scenario,
+    pub positions: Vec<PositionValue>,
+    /// Symbols of the positions that are priced by neither the table nor a model.
+    pub unpriced: Vec<Box<str>>,
+}
+
+impl ScenarioResult {
+    /// Returns the total value of the priced positions.
+    pub fn value ( &self ) -> f64 {
+        self.positions.iter ( ).map ( |p| p.value ).sum ( )
+    }
+
+    /// Returns the total P&L of the priced positions.
+    pub fn pnl ( &self ) -> f64 {
+        self.positions.iter ( ).map ( |p| p.pnl ).sum ( )
+    }
+}
+
+impl Position {
+    /// Returns the DW price and its source in given scenario.
+    ///
+    /// The table is used if it has the column of the date, and the underlying price is within the
+    /// rows of the column. Otherwise, the model is used, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `scenario` - Scenario of the underlying.
+    pub fn price ( &self, scenario: &Scenario ) -> Option<( f64, Source )> {
+        let in_table = self.table.get_column ( scenario.date )
+            .filter ( |column| matches ! (
+                ( column.quotes.first ( ), column.quotes.last ( ) ),
+                ( Some ( first ), Some ( last ) ) if first.underlying_bid <= scenario.underlying && scenario.underlying <= last.underlying_bid
+            ) )
+            .and_then ( |column| column.interpolate_bid ( scenario.underlying ) );
+        match in_table {
+            Some ( price ) => Some ( ( price, Source::Table ) ),
+            None => self.model.as_ref ( )
+                .map ( |model| (
+                    model.terms.fair_value ( scenario.underlying.to_f64 ( ), scenario.date, model.volatility, &model.params ),
+                    Source::Model,
+                ) ),
+        }
+    }
+}
+
+/// Returns the projection of the book for each scenario of the grid of dates and underlying prices,
+/// ordered by date and then by underlying price.
+///
+/// # Arguments
+///
+/// * `positions` - Positions of the book.
+/// * `dates` - Dates to project to, e.g. the day columns of the tables.
+/// * `underlying_grid` - Underlying bids to project to.
+pub fn project ( positions: &[Position], dates: &[NaiveDate], underlying_grid: &[Price] ) -> Vec<ScenarioResult> {
+    dates.iter ( )
+        .flat_map ( |&date| underlying_grid.iter ( ).map ( move |&underlying| Scenario { date, underlying } ) )
+        .map ( |scenario| {
+            let mut result = ScenarioResult {
+                scenario,
+                positions: Vec::new ( ),
+                unpriced: Vec::new ( ),
+            };
+            for position in positions {
+                match position.price ( &scenario ) {
+                    Some ( ( dw_price, source ) ) => {
+                        let value = dw_price * position.quantity as f64;
+                        result.positions.push ( PositionValue {
+                            dw_symbol: position.dw_info.symbol.clone ( ),
+                            dw_price,
+                            source,
+                            value,
+                            pnl: value - position.average_cost * position.quantity as f64,
+                        } );
+                    },
+                    None => result.unpriced.push ( position.dw_info.symbol.clone ( ) ),
+                }
+            }
+            result
+        } )
+        .collect ( )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use crate::{
+        instrument::dw::DWSide,
+        testing::{
+            date,
+            ladder_column,
+            p,
+            table,
+        },
+    };
+
+    fn position ( symbol: &str, model: Option<Model> ) -> Position {
+        Position {
+            dw_info: DWInfo::from_str ( symbol ).unwrap ( ),
+            quantity: 1000,
+            average_cost: 0.50,
+            table: table ( symbol, vec ! [ ladder_column ( 23, 90000, 100, 10, |i| ( Price::new ( 50 + 2 * i, 2 ), None ) ) ] ),
+            model,
+        }
+    }
+
+    #[test]
+    fn test_project ( ) {
+        let model = Model {
+            terms: DWTerms { side: DWSide::C, strike: 950.0, conversion_ratio: 20.0, expiry: date ( 30 ) },
+            volatility: 0.3,
+            params: MarketParams::default ( ),
+        };
+        let positions = [ position ( "S5028C2012D", Some ( model.clone ( ) ) ), position ( "S5013C2012A", None ) ];
+
+        let results = project ( &positions, &[ date ( 23 ), date ( 25 ) ], &[ p ( "905.50" ), p ( "920" ) ] );
+        assert_eq ! ( results.len ( ), 4 );
+        assert_eq ! ( results [ 1 ].scenario, Scenario { date: date ( 23 ), underlying: p ( "920" ) } );
+
+        // within the table
+        let result = &results [ 0 ];
+        assert_eq ! ( result.positions.len ( ), 2 );
+        assert_eq ! ( result.positions [ 0 ].source, Source::Table );
+        assert ! ( ( result.positions [ 0 ].dw_price - 0.61 ).abs ( ) < 1e-9 );
+        assert ! ( ( result.pnl ( ) - 2.0 * 110.0 ).abs ( ) < 1e-6 );
+
+        // out of the table: the model, or unpriced
+        for result in &results [ 1.. ] {
+            assert_eq ! ( result.positions.len ( ), 1 );
+            assert_eq ! ( result.positions [ 0 ].source, Source::Model );
+            assert_eq ! ( result.unpriced, vec ! [ "S5013C2012A".into ( ) ] );
+        }
+        let expected = model.terms.fair_value ( 920.0, date ( 25 ), 0.3, &MarketParams::default ( ) );
+        assert ! ( ( results [ 3 ].positions [ 0 ].dw_price - expected ).abs ( ) < 1e-12 );
+        assert ! ( ( results [ 3 ].value ( ) - expected * 1000.0 ).abs ( ) < 1e-9 );
+    }
+}
@@ -0,0 +1,238 @@
+//! # Market-making quality
+//!
+//! Metrics of how an issuer quotes its DWs, from the price tables: spread, stickiness, gaps in
+//! the matrix, and stability of the quotes between polls.
+
+use std::collections::BTreeMap;
+
+use crate::{
+    price_table::{
+        Match,
+        PriceColumn,
+        PriceTable,
+    },
+    ticks::{
+        TickLadder,
+        DW,
+    },
+};
+
+/// Quality metrics of one or more price tables.
+///
+/// The metrics are kept as totals, so the quality of several tables can be merged exactly.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Quality {
+    /// Number of quotes.
+    pub quotes: usize,
+    /// Number of quotes with the ask.
+    pub two_sided_quotes: usize,
+    /// Total DW ticks between bid and ask of the two-sided quotes.
+    pub spread_ticks: i64,
+    /// Total underlying ticks spanned by the columns.
+    pub underlying_ticks: i64,
+    /// Total DW ticks of the bid spanned by the columns.
+    pub dw_ticks: i64,
+    /// Adjacent rows where the DW bid skips at least one DW tick.
+    pub dw_tick_gaps: usize,
+    /// Adjacent rows wider apart than the row step of the column, across which the DW bid changes.
+    pub missing_rows: usize,
+}
+
+impl Quality {
+    /// Returns the average DW ticks between bid and ask. None if no ask is quoted.
+    pub fn average_spread_ticks ( &self ) -> Option<f64> {
+        match self.two_sided_quotes {
+            0 => None,
+            quotes => Some ( self.spread_ticks as f64 / quotes as f64 ),
+        }
+    }
+
+    /// Returns the average underlying ticks that one DW tick spans. None if the DW bid never moves.
+    pub fn stickiness ( &self ) -> Option<f64> {
+        match self.dw_ticks {
+            0 => None,
+            dw_ticks => Some ( self.underlying_ticks as f64 / dw_ticks as f64 ),
+        }
+    }
+
+    /// Merges the totals of other quality into this one.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Quality of other tables.
+    pub fn merge ( &mut self, other: &Quality ) {
+        self.quotes += other.quotes;
+        self.two_sided_quotes += other.two_sided_quotes;
+        self.spread_ticks += other.spread_ticks;
+        self.underlying_ticks += other.underlying_ticks;
+        self.dw_ticks += other.dw_ticks;
+        self.dw_tick_gaps += other.dw_tick_gaps;
+        self.missing_rows += other.missing_rows;
+    }
+}
+
+/// Returns the quality of the column.
+///
+/// # Arguments
+///
+/// * `column` - Column of the table.
+/// * `ladder` - Tick ladder of the underlying.
+pub fn get_column_quality ( column: &PriceColumn, ladder: &TickLadder ) -> Quality {
+    let quotes = &column.quotes;
+    let mut quality = Quality {
+        quotes: quotes.len ( ),
+        ..Quality::default ( )
+    };
+
+    for quote in quotes {
+        if let Some ( ask ) = quote.ask {
+            quality.two_sided_quotes += 1;
+            quality.spread_ticks += DW.ticks_between ( quote.bid, ask );
+        }
+    }
+
+    if let ( Some ( first ), Some ( last ) ) = ( quotes.first ( ), quotes.last ( ) ) {
+        quality.underlying_ticks = ladder.ticks_between ( first.underlying_bid, last.underlying_bid );
+        quality.dw_ticks = DW.ticks_between ( first.bid, last.bid ).abs ( );
+    }
+
+    let row_step = quotes.windows ( 2 )
+        .map ( |pair| ladder.ticks_between ( pair [ 0 ].underlying_bid, pair [ 1 ].underlying_bid ) )
+        .filter ( |&ticks| ticks > 0 )
+        .min ( );
+    for pair in quotes.windows ( 2 ) {
+        let dw_ticks = DW.ticks_between ( pair [ 0 ].bid, pair [ 1 ].bid );
+        if dw_ticks.abs ( ) > 1 {
+            quality.dw_tick_gaps += 1;
+        }
+        if dw_ticks != 0 && Some ( ladder.ticks_between ( pair [ 0 ].underlying_bid, pair [ 1 ].underlying_bid ) ) > row_step {
+            quality.missing_rows += 1;
+        }
+    }
+
+    quality
+}
+
+/// Returns the quality of all columns of the table.
+///
+/// # Arguments
+///
+/// * `table` - Price table, normalized to [crate::convention::QuoteConvention::NORMALIZED].
+/// * `ladder` - Tick ladder of the underlying.
+pub fn get_quality ( table: &PriceTable, ladder: &TickLadder ) -> Quality {
+    table.columns.iter ( )
+        .fold ( Quality::default ( ), |mut quality, column| {
+            quality.merge ( &get_column_quality ( column, ladder ) );
+            quality
+        } )
+}
+
+/// Returns the quality merged per issuer.
+///
+/// # Arguments
+///
+/// * `qualities` - Pairs of the broker ID of the issuer, and the quality of its DW.
+pub fn get_issuer_quality<I: IntoIterator<Item = ( u8, Quality )>> ( qualities: I ) -> BTreeMap<u8, Quality> {
+    let mut issuers = BTreeMap::<u8, Quality>::new ( );
+    for ( broker_id, quality ) in qualities {
+        issuers.entry ( broker_id ).or_default ( ).merge ( &quality );
+    }
+    issuers
+}
+
+/// Stability of the quotes between two polls of the same DW.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stability {
+    /// Quotes found in both polls, at the same date and underlying price.
+    pub compared: usize,
+    /// Compared quotes with a different bid or ask.
+    pub changed: usize,
+    /// Quotes of the previous poll not found in the current one.
+    pub dropped: usize,
+}
+
+impl Stability {
+    /// Returns the ratio of the compared quotes that are unchanged. None if nothing is compared.
+    pub fn unchanged_ratio ( &self ) -> Option<f64> {
+        match self.compared {
+            0 => None,
+            compared => Some ( ( compared - self.changed ) as f64 / compared as f64 ),
+        }
+    }
+}
+
+/// Returns the stability of the quotes from the previous poll to the current one.
+///
+/// # Arguments
+///
+/// * `previous` - Table of the previous poll.
+/// * `current` - Table of the current poll.
+pub fn get_stability ( previous: &PriceTable, current: &PriceTable ) -> Stability {
+    let mut stability = Stability::default ( );
+    for column in &previous.columns {
+        for quote in &column.quotes {
+            match current.get_quote ( column.date, quote.underlying_bid, Match::Exact ) {
+                Some ( other ) => {
+                    stability.compared += 1;
+                    if other.bid != quote.bid || other.ask != quote.ask {
+                        stability.changed += 1;
+                    }
+                },
+                None => stability.dropped += 1,
+            }
+        }
+    }
+    stability
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        testing::column_with_asks,
+        ticks::SET50_INDEX,
+    };
+
+    fn table ( rows: &[(&str, &str, Option<&str>)] ) -> PriceTable {
+        crate::testing::table ( "S5028C2103A", vec ! [ column_with_asks ( 23, rows ) ] )
+    }
+
+    #[test]
+    fn test_get_quality ( ) {
+        // compressed rows: head and tail of each DW bid
+        let compressed = table ( &[
+            ( "900.00", "0.50", Some ( "0.51" ) ),
+            ( "900.49", "0.50", Some ( "0.51" ) ),
+            ( "900.50", "0.51", Some ( "0.53" ) ),
+            ( "900.99", "0.51", Some ( "0.53" ) ),
+            ( "901.00", "0.53", None ),
+        ] );
+        let quality = get_quality ( &compressed, &SET50_INDEX );
+        assert_eq ! ( quality.quotes, 5 );
+        assert_eq ! ( quality.average_spread_ticks ( ), Some ( 1.5 ) );
+        assert_eq ! ( quality.stickiness ( ), Some ( 100.0 / 3.0 ) );
+        assert_eq ! ( quality.dw_tick_gaps, 1 );
+        assert_eq ! ( quality.missing_rows, 0 );
+
+        // one row missing from a step of 0.50
+        let sparse = table ( &[ ( "900.00", "0.50", None ), ( "900.50", "0.51", None ), ( "901.50", "0.53", None ) ] );
+        let quality = get_quality ( &sparse, &SET50_INDEX );
+        assert_eq ! ( quality.average_spread_ticks ( ), None );
+        assert_eq ! ( quality.missing_rows, 1 );
+
+        let issuers = get_issuer_quality ( vec ! [ ( 28, get_quality ( &compressed, &SET50_INDEX ) ), ( 28, quality ), ( 13, quality ) ] );
+        assert_eq ! ( issuers [ &28 ].quotes, 8 );
+        assert_eq ! ( issuers [ &28 ].average_spread_ticks ( ), Some ( 1.5 ) );
+        assert_eq ! ( issuers [ &13 ].quotes, 3 );
+    }
+
+    #[test]
+    fn test_get_stability ( ) {
+        let previous = table ( &[ ( "900.00", "0.50", Some ( "0.51" ) ), ( "900.50", "0.51", Some ( "0.52" ) ), ( "901.00", "0.52", None ) ] );
+        let current = table ( &[ ( "900.00", "0.50", Some ( "0.51" ) ), ( "900.50", "0.51", Some ( "0.53" ) ) ] );
+
+        let stability = get_stability ( &previous, &current );
+        assert_eq ! ( stability, Stability { compared: 2, changed: 1, dropped: 1 } );
+        assert_eq ! ( stability.unchanged_ratio ( ), Some ( 0.5 ) );
+    }
+}
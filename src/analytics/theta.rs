@@ -0,0 +1,153 @@
+//! # Time decay from price tables
+//!
+//! The day columns of an issuer's table, e.g. BidT1..T5 of DW06 or the date columns of DW13 and
+//! DW28, encode the time decay expected by the issuer. [Theta] is the change of the DW bid
+//! between two consecutive columns at the same underlying price.
+
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+
+use crate::{
+    price::Price,
+    price_table::{
+        Match,
+        PriceTable,
+    },
+    ticks::DW,
+};
+
+/// Change of the DW bid between two consecutive columns at one underlying price.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theta {
+    pub underlying: Price,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub bid_from: Price,
+    pub bid_to: Price,
+}
+
+impl Theta {
+    /// Returns the calendar days between the columns.
+    pub fn days ( &self ) -> i64 {
+        ( self.to - self.from ).num_days ( )
+    }
+
+    /// Returns the change of the DW bid. Negative if the DW loses value.
+    pub fn change ( &self ) -> f64 {
+        self.bid_to.to_f64 ( ) - self.bid_from.to_f64 ( )
+    }
+
+    /// Returns the change of the DW bid per calendar day.
+    pub fn per_day ( &self ) -> f64 {
+        match self.days ( ) {
+            0 => 0.0,
+            days => self.change ( ) / days as f64,
+        }
+    }
+
+    /// Returns the change of the DW bid in DW ticks. Negative if the DW loses ticks.
+    pub fn ticks ( &self ) -> i64 {
+        DW.ticks_between ( self.bid_from, self.bid_to )
+    }
+}
+
+/// Returns the theta between each pair of consecutive columns at given underlying price.
+///
+/// The DW bid of each column is the quote of the highest underlying price not above the given one.
+/// Pairs without a quote in either column are skipped.
+///
+/// # Arguments
+///
+/// * `table` - Price table, normalized to [crate::convention::QuoteConvention::NORMALIZED].
+/// * `underlying` - Underlying bid.
+pub fn get_theta ( table: &PriceTable, underlying: Price ) -> Vec<Theta> {
+    table.columns.windows ( 2 )
+        .filter_map ( |pair| {
+            let bid_from = pair [ 0 ].get_quote ( underlying, Match::Floor )?.bid;
+            let bid_to = pair [ 1 ].get_quote ( underlying, Match::Floor )?.bid;
+            Some ( Theta {
+                underlying,
+                from: pair [ 0 ].date,
+                to: pair [ 1 ].date,
+                bid_from,
+                bid_to,
+            } )
+        } )
+        .collect ( )
+}
+
+/// Returns the theta of every underlying price of the first column. See [get_theta].
+///
+/// # Arguments
+///
+/// * `table` - Price table, normalized to [crate::convention::QuoteConvention::NORMALIZED].
+pub fn get_theta_by_level ( table: &PriceTable ) -> BTreeMap<Price, Vec<Theta>> {
+    table.columns.first ( )
+        .map ( |column| column.quotes.iter ( )
+            .map ( |quote| ( quote.underlying_bid, get_theta ( table, quote.underlying_bid ) ) )
+            .collect ( ) )
+        .unwrap_or_default ( )
+}
+
+/// Returns the theta from the first column to the next one at given spot, if the DW loses at
+/// least one tick overnight. None if it does not, or the table has less than 2 columns.
+///
+/// # Arguments
+///
+/// * `table` - Price table, normalized to [crate::convention::QuoteConvention::NORMALIZED].
+/// * `spot` - Current underlying bid.
+pub fn get_overnight_tick_loss ( table: &PriceTable, spot: Price ) -> Option<Theta> {
+    get_theta ( table, spot ).into_iter ( )
+        .next ( )
+        .filter ( |theta| theta.from == table.columns [ 0 ].date && theta.to == table.columns [ 1 ].date )
+        .filter ( |theta| theta.ticks ( ) < 0 )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{
+        column,
+        date,
+        p,
+    };
+
+    fn table ( ) -> PriceTable {
+        crate::testing::table ( "S5028C2012D", vec ! [
+            column ( 23, &[ ( "900.00", "0.55" ), ( "901.00", "0.57" ) ] ),
+            column ( 24, &[ ( "900.00", "0.55" ), ( "901.00", "0.56" ) ] ),
+            column ( 28, &[ ( "900.00", "0.52" ), ( "901.00", "0.54" ) ] ),
+        ] )
+    }
+
+    #[test]
+    fn test_get_theta ( ) {
+        let thetas = get_theta ( &table ( ), p ( "900.50" ) );
+        assert_eq ! ( thetas.len ( ), 2 );
+
+        assert_eq ! ( ( thetas [ 0 ].from, thetas [ 0 ].to ), ( date ( 23 ), date ( 24 ) ) );
+        assert_eq ! ( thetas [ 0 ].ticks ( ), 0 );
+
+        assert_eq ! ( thetas [ 1 ].days ( ), 4 );
+        assert_eq ! ( thetas [ 1 ].ticks ( ), -3 );
+        assert ! ( ( thetas [ 1 ].per_day ( ) + 0.0075 ).abs ( ) < 1e-9 );
+
+        assert ! ( get_theta ( &table ( ), p ( "899" ) ).is_empty ( ) );
+    }
+
+    #[test]
+    fn test_get_theta_by_level ( ) {
+        let thetas = get_theta_by_level ( &table ( ) );
+        assert_eq ! ( thetas.keys ( ).copied ( ).collect::<Vec<Price>> ( ), vec ! [ p ( "900" ), p ( "901" ) ] );
+        assert_eq ! ( thetas [ &p ( "901" ) ] [ 0 ].ticks ( ), -1 );
+    }
+
+    #[test]
+    fn test_get_overnight_tick_loss ( ) {
+        assert_eq ! ( get_overnight_tick_loss ( &table ( ), p ( "900.50" ) ), None );
+
+        let loss = get_overnight_tick_loss ( &table ( ), p ( "901.00" ) ).unwrap ( );
+        assert_eq ! ( ( loss.bid_from, loss.bid_to ), ( p ( "0.57" ), p ( "0.56" ) ) );
+    }
+}
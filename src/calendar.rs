@@ -0,0 +1,293 @@
+//! # SET trading calendar
+//!
+//! Trading days of SET: Mon-Fri, except the holidays announced by SET. The built-in holidays are
+//! updatable with [update_set_holidays], e.g. when SET announces a special holiday.
+
+use std::{
+    collections::BTreeSet,
+    sync::{
+        Arc,
+        RwLock,
+    },
+};
+
+use chrono::{
+    Datelike,
+    Duration,
+    NaiveDate,
+    NaiveDateTime,
+    NaiveTime,
+    Weekday,
+};
+
+use lazy_static::lazy_static;
+
+/// Built-in SET holidays, as (year, month, day).
+///
+/// Special holidays announced later in a year are added by [update_set_holidays].
+const SET_HOLIDAYS: &[(i32, u32, u32)] = &[
+    // 2020
+    ( 2020, 1, 1 ), ( 2020, 2, 10 ), ( 2020, 4, 6 ), ( 2020, 5, 1 ), ( 2020, 5, 4 ), ( 2020, 5, 6 ),
+    ( 2020, 6, 3 ), ( 2020, 7, 6 ), ( 2020, 7, 27 ), ( 2020, 7, 28 ), ( 2020, 8, 12 ), ( 2020, 9, 4 ),
+    ( 2020, 9, 7 ), ( 2020, 10, 13 ), ( 2020, 10, 23 ), ( 2020, 11, 19 ), ( 2020, 11, 20 ), ( 2020, 12, 7 ),
+    ( 2020, 12, 10 ), ( 2020, 12, 31 ),
+    // 2021
+    ( 2021, 1, 1 ), ( 2021, 2, 12 ), ( 2021, 2, 26 ), ( 2021, 4, 6 ), ( 2021, 4, 12 ), ( 2021, 4, 13 ),
+    ( 2021, 4, 14 ), ( 2021, 4, 15 ), ( 2021, 5, 3 ), ( 2021, 5, 4 ), ( 2021, 5, 26 ), ( 2021, 6, 3 ),
+    ( 2021, 7, 26 ), ( 2021, 7, 28 ), ( 2021, 8, 12 ), ( 2021, 9, 24 ), ( 2021, 10, 13 ), ( 2021, 10, 22 ),
+    ( 2021, 12, 6 ), ( 2021, 12, 10 ), ( 2021, 12, 31 ),
+    // 2022
+    ( 2022, 1, 3 ), ( 2022, 2, 16 ), ( 2022, 4, 6 ), ( 2022, 4, 13 ), ( 2022, 4, 14 ), ( 2022, 4, 15 ),
+    ( 2022, 5, 2 ), ( 2022, 5, 4 ), ( 2022, 5, 16 ), ( 2022, 6, 3 ), ( 2022, 7, 13 ), ( 2022, 7, 28 ),
+    ( 2022, 7, 29 ), ( 2022, 8, 12 ), ( 2022, 10, 13 ), ( 2022, 10, 14 ), ( 2022, 10, 24 ), ( 2022, 12, 5 ),
+    ( 2022, 12, 12 ), ( 2022, 12, 30 ),
+    // 2023
+    ( 2023, 1, 2 ), ( 2023, 3, 6 ), ( 2023, 4, 6 ), ( 2023, 4, 13 ), ( 2023, 4, 14 ), ( 2023, 4, 17 ),
+    ( 2023, 5, 1 ), ( 2023, 5, 4 ), ( 2023, 6, 5 ), ( 2023, 7, 28 ), ( 2023, 8, 1 ), ( 2023, 8, 14 ),
+    ( 2023, 10, 13 ), ( 2023, 10, 23 ), ( 2023, 12, 5 ), ( 2023, 12, 11 ), ( 2023, 12, 29 ),
+    // 2024
+    ( 2024, 1, 1 ), ( 2024, 2, 26 ), ( 2024, 4, 8 ), ( 2024, 4, 12 ), ( 2024, 4, 15 ), ( 2024, 4, 16 ),
+    ( 2024, 5, 1 ), ( 2024, 5, 6 ), ( 2024, 5, 22 ), ( 2024, 6, 3 ), ( 2024, 7, 22 ), ( 2024, 7, 29 ),
+    ( 2024, 8, 12 ), ( 2024, 10, 14 ), ( 2024, 10, 23 ), ( 2024, 12, 5 ), ( 2024, 12, 10 ), ( 2024, 12, 31 ),
+    // 2025
+    ( 2025, 1, 1 ), ( 2025, 2, 12 ), ( 2025, 4, 7 ), ( 2025, 4, 14 ), ( 2025, 4, 15 ), ( 2025, 5, 1 ),
+    ( 2025, 5, 5 ), ( 2025, 5, 12 ), ( 2025, 6, 2 ), ( 2025, 6, 3 ), ( 2025, 7, 10 ), ( 2025, 7, 28 ),
+    ( 2025, 8, 11 ), ( 2025, 8, 12 ), ( 2025, 10, 13 ), ( 2025, 10, 23 ), ( 2025, 12, 5 ), ( 2025, 12, 10 ),
+    ( 2025, 12, 31 ),
+    // 2026
+    ( 2026, 1, 1 ), ( 2026, 3, 3 ), ( 2026, 4, 6 ), ( 2026, 4, 13 ), ( 2026, 4, 14 ), ( 2026, 4, 15 ),
+    ( 2026, 5, 1 ), ( 2026, 5, 4 ), ( 2026, 6, 1 ), ( 2026, 6, 3 ), ( 2026, 7, 28 ), ( 2026, 7, 29 ),
+    ( 2026, 8, 12 ), ( 2026, 10, 13 ), ( 2026, 10, 23 ), ( 2026, 12, 7 ), ( 2026, 12, 10 ), ( 2026, 12, 31 ),
+];
+
+lazy_static ! {
+    static ref SET_CALENDAR : RwLock<Arc<TradingCalendar>> = RwLock::new ( Arc::new ( TradingCalendar::new (
+        SET_HOLIDAYS.iter ( )
+            .filter_map ( |&(year, month, day)| NaiveDate::from_ymd_opt ( year, month, day ) )
+    ) ) );
+}
+
+/// Adds given holidays to the SET calendar. The calendars returned by [TradingCalendar::set]
+/// before are left unchanged.
+///
+/// # Arguments
+///
+/// * `holidays` - Holidays announced by SET.
+pub fn update_set_holidays<I: IntoIterator<Item = NaiveDate>> ( holidays: I ) {
+    let mut calendar = SET_CALENDAR.write ( )
+        .expect ( "Failed to lock the SET calendar" );
+    Arc::make_mut ( &mut calendar )
+        .holidays
+        .extend ( holidays );
+}
+
+/// Calendar of trading days: Mon-Fri, except the holidays.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TradingCalendar {
+    holidays: BTreeSet<NaiveDate>,
+}
+
+impl TradingCalendar {
+    /// Returns the calendar of given holidays.
+    ///
+    /// # Arguments
+    ///
+    /// * `holidays` - Holidays on Mon-Fri.
+    pub fn new<I: IntoIterator<Item = NaiveDate>> ( holidays: I ) -> Self {
+        TradingCalendar {
+            holidays: holidays.into_iter ( ).collect ( ),
+        }
+    }
+
+    /// Returns the SET calendar, with the built-in holidays and the ones from [update_set_holidays].
+    ///
+    /// The calendar is shared, not copied, until the holidays are updated.
+    pub fn set ( ) -> Arc<Self> {
+        SET_CALENDAR.read ( )
+            .expect ( "Failed to lock the SET calendar" )
+            .clone ( )
+    }
+
+    /// Returns the holidays, sorted.
+    pub fn holidays ( &self ) -> impl Iterator<Item = &NaiveDate> {
+        self.holidays.iter ( )
+    }
+
+    /// Returns true if given date is a trading day.
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - Date to check.
+    pub fn is_trading_day ( &self, date: NaiveDate ) -> bool {
+        ! matches ! ( date.weekday ( ), Weekday::Sat | Weekday::Sun ) && ! self.holidays.contains ( &date )
+    }
+
+    /// Returns the first trading day after given date.
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - Date to count from, excluded.
+    pub fn next_trading_day ( &self, date: NaiveDate ) -> NaiveDate {
+        let mut date = date + Duration::days ( 1 );
+        while ! self.is_trading_day ( date ) {
+            date += Duration::days ( 1 );
+        }
+        date
+    }
+
+    /// Returns the last trading day before given date.
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - Date to count from, excluded.
+    pub fn previous_trading_day ( &self, date: NaiveDate ) -> NaiveDate {
+        let mut date = date - Duration::days ( 1 );
+        while ! self.is_trading_day ( date ) {
+            date -= Duration::days ( 1 );
+        }
+        date
+    }
+
+    /// Returns given date if it is a trading day, or the next one.
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - Date to roll.
+    pub fn roll_forward ( &self, date: NaiveDate ) -> NaiveDate {
+        if self.is_trading_day ( date ) {
+            date
+        } else {
+            self.next_trading_day ( date )
+        }
+    }
+
+    /// Returns the number of trading days after [from] up to and including [to]. Negative if [to]
+    /// is before [from].
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - Date to count from, excluded.
+    /// * `to` - Date to count to, included.
+    pub fn trading_days_between ( &self, from: NaiveDate, to: NaiveDate ) -> i64 {
+        if to < from {
+            return - self.trading_days_between ( to, from );
+        }
+        from.iter_days ( )
+            .skip ( 1 )
+            .take_while ( |date| *date <= to )
+            .filter ( |date| self.is_trading_day ( *date ) )
+            .count ( ) as i64
+    }
+
+    /// Returns the trading date/time nearest to given date/time, like
+    /// [crate::get_working_date_time_from] but skipping the holidays.
+    ///
+    /// If given date/time is on a trading day before 16:30, it is returned. Otherwise, the start of
+    /// the next trading day is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `datetime` - The base date/time.
+    pub fn get_trading_date_time_from ( &self, datetime: NaiveDateTime ) -> NaiveDateTime {
+        let cutoff = NaiveTime::from_hms_opt ( 16, 30, 0 ).expect ( "Invalid cutoff time" );
+        if self.is_trading_day ( datetime.date ( ) ) && datetime.time ( ) < cutoff {
+            datetime
+        } else {
+            self.next_trading_day ( datetime.date ( ) ).and_time ( NaiveTime::MIN )
+        }
+    }
+
+    /// Returns the dates inconsistent with the calendar: listed holidays on which the calendar
+    /// trades, and quoted dates on which it does not, e.g. from the DW28 live matrix.
+    ///
+    /// # Arguments
+    ///
+    /// * `holidays` - Dates listed as holidays.
+    /// * `trading_dates` - Dates listed as trading days.
+    pub fn mismatches ( &self, holidays: &[NaiveDate], trading_dates: &[NaiveDate] ) -> Vec<NaiveDate> {
+        let mut dates = holidays.iter ( )
+            .filter ( |date| self.is_trading_day ( **date ) )
+            .chain ( trading_dates.iter ( ).filter ( |date| ! self.is_trading_day ( **date ) ) )
+            .copied ( )
+            .collect::<Vec<NaiveDate>> ( );
+        dates.sort ( );
+        dates.dedup ( );
+        dates
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date ( month: u32, day: u32 ) -> NaiveDate {
+        NaiveDate::from_ymd_opt ( 2020, month, day ).unwrap ( )
+    }
+
+    #[test]
+    fn test_is_trading_day ( ) {
+        let calendar = TradingCalendar::set ( );
+        assert ! ( calendar.is_trading_day ( date ( 12, 25 ) ) );
+        assert ! ( ! calendar.is_trading_day ( date ( 12, 26 ) ) );
+        assert ! ( ! calendar.is_trading_day ( date ( 12, 31 ) ) );
+        assert ! ( ! calendar.is_trading_day ( date ( 4, 6 ) ) );
+    }
+
+    #[test]
+    fn test_is_trading_day_after_2021 ( ) {
+        let calendar = TradingCalendar::set ( );
+        // Songkran
+        assert ! ( ! calendar.is_trading_day ( NaiveDate::from_ymd_opt ( 2024, 4, 15 ).unwrap ( ) ) );
+        // Chakri
+        assert ! ( ! calendar.is_trading_day ( NaiveDate::from_ymd_opt ( 2026, 4, 6 ).unwrap ( ) ) );
+        assert ! ( calendar.is_trading_day ( NaiveDate::from_ymd_opt ( 2026, 4, 7 ).unwrap ( ) ) );
+        assert_eq ! ( calendar.next_trading_day ( NaiveDate::from_ymd_opt ( 2026, 4, 10 ).unwrap ( ) ), NaiveDate::from_ymd_opt ( 2026, 4, 16 ).unwrap ( ) );
+    }
+
+    #[test]
+    fn test_set_calendar_shared ( ) {
+        assert ! ( Arc::ptr_eq ( &TradingCalendar::set ( ), &TradingCalendar::set ( ) ) );
+    }
+
+    #[test]
+    fn test_next_previous_trading_day ( ) {
+        let calendar = TradingCalendar::set ( );
+        assert_eq ! ( calendar.next_trading_day ( date ( 12, 30 ) ), NaiveDate::from_ymd_opt ( 2021, 1, 4 ).unwrap ( ) );
+        assert_eq ! ( calendar.previous_trading_day ( NaiveDate::from_ymd_opt ( 2021, 1, 4 ).unwrap ( ) ), date ( 12, 30 ) );
+        assert_eq ! ( calendar.next_trading_day ( date ( 12, 9 ) ), date ( 12, 11 ) );
+        assert_eq ! ( calendar.roll_forward ( date ( 12, 10 ) ), date ( 12, 11 ) );
+        assert_eq ! ( calendar.roll_forward ( date ( 12, 11 ) ), date ( 12, 11 ) );
+    }
+
+    #[test]
+    fn test_trading_days_between ( ) {
+        let calendar = TradingCalendar::set ( );
+        // 24, 25, 28, 29, 30 Dec
+        assert_eq ! ( calendar.trading_days_between ( date ( 12, 23 ), NaiveDate::from_ymd_opt ( 2021, 1, 1 ).unwrap ( ) ), 5 );
+        assert_eq ! ( calendar.trading_days_between ( date ( 12, 30 ), date ( 12, 23 ) ), -5 );
+        assert_eq ! ( calendar.trading_days_between ( date ( 12, 23 ), date ( 12, 23 ) ), 0 );
+    }
+
+    #[test]
+    fn test_get_trading_date_time_from ( ) {
+        let calendar = TradingCalendar::set ( );
+        let datetime = |month, day, hour, minute| date ( month, day ).and_hms_opt ( hour, minute, 0 ).unwrap ( );
+        assert_eq ! ( calendar.get_trading_date_time_from ( datetime ( 12, 30, 16, 29 ) ), datetime ( 12, 30, 16, 29 ) );
+        assert_eq ! ( calendar.get_trading_date_time_from ( datetime ( 12, 30, 16, 30 ) ), NaiveDate::from_ymd_opt ( 2021, 1, 4 ).unwrap ( ).and_hms_opt ( 0, 0, 0 ).unwrap ( ) );
+        assert_eq ! ( calendar.get_trading_date_time_from ( datetime ( 12, 10, 10, 0 ) ), datetime ( 12, 11, 0, 0 ) );
+    }
+
+    #[test]
+    fn test_update_and_mismatches ( ) {
+        let special = NaiveDate::from_ymd_opt ( 2099, 1, 2 ).unwrap ( );
+        let before = TradingCalendar::set ( );
+        assert ! ( before.is_trading_day ( special ) );
+        update_set_holidays ( [ special ] );
+        assert ! ( ! TradingCalendar::set ( ).is_trading_day ( special ) );
+        assert ! ( before.is_trading_day ( special ) );
+
+        let calendar = TradingCalendar::new ( [ date ( 12, 31 ) ] );
+        assert_eq ! ( calendar.holidays ( ).count ( ), 1 );
+        assert_eq ! ( calendar.mismatches ( &[ date ( 12, 31 ), date ( 12, 30 ) ], &[ date ( 12, 29 ), date ( 12, 26 ) ] ), vec ! [ date ( 12, 26 ), date ( 12, 30 ) ] );
+    }
+}
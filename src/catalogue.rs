@@ -0,0 +1,191 @@
+//! # Issuer DW catalogues
+//!
+//! Listed DWs of each issuer, e.g. from [crate::dw28::DW28::get_catalogue], cached with
+//! [update_catalogue] to validate DW symbols before requesting their price tables. A DW that is
+//! not listed is either unknown, e.g. a typo, or already expired.
+
+use std::{
+    collections::{
+        BTreeMap,
+        HashMap,
+    },
+    sync::RwLock,
+};
+
+use chrono::NaiveDateTime;
+
+use lazy_static::lazy_static;
+
+use crate::instrument::dw::{
+    DWInfo,
+    Error,
+};
+
+/// Maximum number of close matches suggested for an unknown DW.
+pub const MAX_SUGGESTIONS: usize = 5;
+
+/// Maximum number of edited characters of a close match.
+pub const MAX_SUGGESTION_DISTANCE: usize = 2;
+
+lazy_static ! {
+    static ref CATALOGUES : RwLock<HashMap<u8, Catalogue>> = RwLock::new ( HashMap::new ( ) );
+}
+
+/// Caches given catalogue, replacing the one of the same issuer.
+///
+/// # Arguments
+///
+/// * `catalogue` - Catalogue of an issuer.
+pub fn update_catalogue ( catalogue: Catalogue ) {
+    CATALOGUES.write ( )
+        .expect ( "Failed to lock the catalogues" )
+        .insert ( catalogue.broker_id, catalogue );
+}
+
+/// Returns the cached catalogue of given issuer, if any.
+///
+/// # Arguments
+///
+/// * `broker_id` - Broker id of the issuer.
+pub fn get_catalogue ( broker_id: u8 ) -> Option<Catalogue> {
+    CATALOGUES.read ( )
+        .expect ( "Failed to lock the catalogues" )
+        .get ( &broker_id )
+        .cloned ( )
+}
+
+/// Listed DWs of an issuer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Catalogue {
+    pub broker_id: u8,
+    /// Time of the last update by the issuer, if published.
+    pub updated: Option<NaiveDateTime>,
+    /// Listed DW symbols, with the RIC if published.
+    pub symbols: BTreeMap<Box<str>, Option<Box<str>>>,
+}
+
+impl Catalogue {
+    /// Returns the catalogue of given symbols and RICs.
+    ///
+    /// # Arguments
+    ///
+    /// * `broker_id` - Broker id of the issuer.
+    /// * `updated` - Time of the last update by the issuer.
+    /// * `symbols` - Pairs of DW symbol and RIC.
+    pub fn new<I: IntoIterator<Item = ( Box<str>, Option<Box<str>> )>> ( broker_id: u8, updated: Option<NaiveDateTime>, symbols: I ) -> Self {
+        Catalogue {
+            broker_id,
+            updated,
+            symbols: symbols.into_iter ( ).collect ( ),
+        }
+    }
+
+    /// Returns true if given DW symbol is listed.
+    ///
+    /// # Arguments
+    ///
+    /// * `dw_symbol` - DW symbol.
+    pub fn contains ( &self, dw_symbol: &str ) -> bool {
+        self.symbols.contains_key ( dw_symbol )
+    }
+
+    /// Returns the RIC of given DW symbol, if listed and published.
+    ///
+    /// # Arguments
+    ///
+    /// * `dw_symbol` - DW symbol.
+    pub fn get_ric ( &self, dw_symbol: &str ) -> Option<&str> {
+        self.symbols.get ( dw_symbol )?.as_deref ( )
+    }
+
+    /// Returns the listed symbols close to given one, nearest first, up to [MAX_SUGGESTIONS].
+    ///
+    /// # Arguments
+    ///
+    /// * `dw_symbol` - DW symbol, e.g. with a typo.
+    pub fn suggest ( &self, dw_symbol: &str ) -> Vec<Box<str>> {
+        let mut matches = self.symbols.keys ( )
+            .map ( |symbol| ( edit_distance ( dw_symbol, symbol ), symbol ) )
+            .filter ( |( distance, _ )| *distance <= MAX_SUGGESTION_DISTANCE )
+            .collect::<Vec<_>> ( );
+        matches.sort ( );
+        matches.into_iter ( )
+            .take ( MAX_SUGGESTIONS )
+            .map ( |( _, symbol )| symbol.clone ( ) )
+            .collect ( )
+    }
+
+    /// Returns [Error::UnknownDW] with the close matches if given DW is not listed.
+    ///
+    /// # Arguments
+    ///
+    /// * `dw_info` - DW info to be validated.
+    pub fn validate ( &self, dw_info: &DWInfo ) -> Result<( ), Error> {
+        if self.contains ( &dw_info.symbol ) {
+            Ok ( ( ) )
+        } else {
+            Err ( Error::UnknownDW {
+                symbol: dw_info.symbol.clone ( ),
+                suggestions: self.suggest ( &dw_info.symbol ),
+            } )
+        }
+    }
+}
+
+/// Returns the Levenshtein distance of given strings, in chars.
+fn edit_distance ( a: &str, b: &str ) -> usize {
+    let b = b.chars ( ).collect::<Vec<char>> ( );
+    let mut row = ( 0..=b.len ( ) ).collect::<Vec<usize>> ( );
+    for ( i, ca ) in a.chars ( ).enumerate ( ) {
+        let mut diagonal = row [ 0 ];
+        row [ 0 ] = i + 1;
+        for ( j, cb ) in b.iter ( ).enumerate ( ) {
+            let substitution = diagonal + usize::from ( ca != *cb );
+            diagonal = row [ j + 1 ];
+            row [ j + 1 ] = substitution.min ( row [ j ] + 1 ).min ( diagonal + 1 );
+        }
+    }
+    row [ b.len ( ) ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn catalogue ( broker_id: u8, symbols: &[&str] ) -> Catalogue {
+        Catalogue::new ( broker_id, None, symbols.iter ( ).map ( |s| ( ( *s ).into ( ), None ) ) )
+    }
+
+    #[test]
+    fn test_edit_distance ( ) {
+        assert_eq ! ( edit_distance ( "S5028C2012D", "S5028C2012D" ), 0 );
+        assert_eq ! ( edit_distance ( "S5028C2012D", "S5028P2012D" ), 1 );
+        assert_eq ! ( edit_distance ( "S5028C212D", "S5028C2012D" ), 1 );
+        assert_eq ! ( edit_distance ( "", "ABC" ), 3 );
+        assert_eq ! ( edit_distance ( "KITTEN", "SITTING" ), 3 );
+    }
+
+    #[test]
+    fn test_validate ( ) {
+        let catalogue = catalogue ( 28, &[ "S5028C2012D", "S5028C2103A", "S5028P2012D", "HSI28C2012C" ] );
+        assert_eq ! ( catalogue.validate ( &DWInfo::from_str ( "S5028C2012D" ).unwrap ( ) ), Ok ( ( ) ) );
+        assert_eq ! ( catalogue.validate ( &DWInfo::from_str ( "S5028C2012E" ).unwrap ( ) ), Err ( Error::UnknownDW {
+            symbol: "S5028C2012E".into ( ),
+            suggestions: vec ! [ "S5028C2012D".into ( ), "S5028P2012D".into ( ) ],
+        } ) );
+        assert_eq ! ( catalogue.suggest ( "XX00C3333Z" ), Vec::<Box<str>>::new ( ) );
+    }
+
+    #[test]
+    fn test_update_catalogue ( ) {
+        // broker id of no issuer, not to affect the other tests
+        assert_eq ! ( get_catalogue ( 99 ), None );
+        update_catalogue ( catalogue ( 99, &[ "ABC99C2012A" ] ) );
+        let dw_info = DWInfo::from_str ( "ABC99C2012B" ).unwrap ( );
+        assert ! ( matches ! ( dw_info.validate ( ), Err ( Error::UnknownDW { .. } ) ) );
+        assert_eq ! ( DWInfo::from_str ( "ABC99C2012A" ).unwrap ( ).validate ( ), Ok ( ( ) ) );
+        // no catalogue to validate against
+        assert_eq ! ( DWInfo::from_str ( "ABC98C2012B" ).unwrap ( ).validate ( ), Ok ( ( ) ) );
+    }
+}
@@ -0,0 +1,126 @@
+//! # Market clock
+//!
+//! The time of SET, in Asia/Bangkok (UTC+7, no daylight saving), whatever the time zone of the
+//! server. The source of the current time is injectable, e.g. [FixedClock] to replay a recorded
+//! date, or in tests.
+
+use std::{
+    cell::RefCell,
+    sync::{
+        Arc,
+        RwLock,
+    },
+};
+
+use chrono::{
+    DateTime,
+    FixedOffset,
+    NaiveDateTime,
+    TimeZone,
+    Utc,
+};
+
+use lazy_static::lazy_static;
+
+/// Offset of Asia/Bangkok from UTC, in seconds.
+pub const BANGKOK_UTC_OFFSET: i32 = 7 * 3600;
+
+/// Returns the time zone of Asia/Bangkok.
+pub fn bangkok ( ) -> FixedOffset {
+    FixedOffset::east_opt ( BANGKOK_UTC_OFFSET ).expect ( "Invalid offset of Asia/Bangkok" )
+}
+
+/// Source of the current time.
+pub trait ClockSource: Send + Sync {
+    /// Returns the current time.
+    fn now ( &self ) -> DateTime<Utc>;
+}
+
+/// The system clock.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl ClockSource for SystemClock {
+    fn now ( &self ) -> DateTime<Utc> {
+        Utc::now ( )
+    }
+}
+
+/// Clock fixed at one point of time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedClock ( pub DateTime<Utc> );
+
+impl FixedClock {
+    /// Returns the clock fixed at given Bangkok date/time.
+    ///
+    /// # Arguments
+    ///
+    /// * `datetime` - Date/time in Asia/Bangkok.
+    pub fn at_bangkok ( datetime: NaiveDateTime ) -> Self {
+        FixedClock ( bangkok ( ).from_local_datetime ( &datetime )
+            .single ( )
+            .expect ( "Invalid Bangkok date/time" )
+            .with_timezone ( &Utc ) )
+    }
+}
+
+impl ClockSource for FixedClock {
+    fn now ( &self ) -> DateTime<Utc> {
+        self.0
+    }
+}
+
+lazy_static ! {
+    static ref CLOCK : RwLock<Arc<dyn ClockSource>> = RwLock::new ( Arc::new ( SystemClock ) );
+}
+
+thread_local ! {
+    static THREAD_CLOCK : RefCell<Option<Arc<dyn ClockSource>>> = RefCell::new ( None );
+}
+
+/// Sets the source of the current time of all threads.
+///
+/// # Arguments
+///
+/// * `source` - Source of the current time.
+pub fn set_clock_source ( source: Arc<dyn ClockSource> ) {
+    *CLOCK.write ( ).expect ( "Failed to lock the clock" ) = source;
+}
+
+/// Sets the source of the current time of the current thread only, over [set_clock_source].
+///
+/// # Arguments
+///
+/// * `source` - Source of the current time. None to use the one of all threads.
+pub fn set_thread_clock_source ( source: Option<Arc<dyn ClockSource>> ) {
+    THREAD_CLOCK.with ( |clock| *clock.borrow_mut ( ) = source );
+}
+
+/// Returns the current time in Asia/Bangkok, from the clock source.
+pub fn now_bangkok ( ) -> DateTime<FixedOffset> {
+    let now = THREAD_CLOCK.with ( |clock| clock.borrow ( ).as_ref ( ).map ( |source| source.now ( ) ) )
+        .unwrap_or_else ( || CLOCK.read ( ).expect ( "Failed to lock the clock" ).now ( ) );
+    now.with_timezone ( &bangkok ( ) )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_now_bangkok ( ) {
+        // 20:00 UTC is 03:00 of the next day in Bangkok
+        let utc = Utc.with_ymd_and_hms ( 2020, 12, 22, 20, 0, 0 ).unwrap ( );
+        set_thread_clock_source ( Some ( Arc::new ( FixedClock ( utc ) ) ) );
+        assert_eq ! ( now_bangkok ( ).naive_local ( ), NaiveDate::from_ymd_opt ( 2020, 12, 23 ).unwrap ( ).and_hms_opt ( 3, 0, 0 ).unwrap ( ) );
+
+        let bangkok = NaiveDate::from_ymd_opt ( 2020, 12, 23 ).unwrap ( ).and_hms_opt ( 12, 0, 0 ).unwrap ( );
+        set_thread_clock_source ( Some ( Arc::new ( FixedClock::at_bangkok ( bangkok ) ) ) );
+        assert_eq ! ( now_bangkok ( ).naive_local ( ), bangkok );
+        assert_eq ! ( now_bangkok ( ).naive_utc ( ), bangkok - chrono::Duration::hours ( 7 ) );
+
+        set_thread_clock_source ( None );
+        assert ! ( ( Utc::now ( ) - now_bangkok ( ).with_timezone ( &Utc ) ).num_seconds ( ).abs ( ) < 2 );
+    }
+}
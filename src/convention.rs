@@ -0,0 +1,218 @@
+//! # Issuer quoting conventions
+//!
+//! Issuers key their tables on different sides of the underlying price. e.g. DW13 publishes the
+//! SET50 call table on the underlying ask, while DW28 publishes the underlying bid explicitly.
+//! [QuoteConvention] describes the table of each issuer, so the tables can be normalized to
+//! [QuoteConvention::NORMALIZED] on output, where the same underlying price means the same thing.
+
+use std::collections::{
+    BTreeMap,
+    HashMap,
+};
+
+use crate::{
+    instrument::dw::{
+        DWInfo,
+        DWSide,
+    },
+    price::Price,
+    price_table::{
+        PriceColumn,
+        PriceTable,
+    },
+    ticks::{
+        Snap,
+        TickLadder,
+        SET50_HALF_POINT,
+    },
+};
+
+/// Side of the underlying price that a table is keyed on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnderlyingKey {
+    Bid,
+    Ask,
+    Mid,
+}
+
+/// Quoting convention of an issuer's table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuoteConvention {
+    /// Side of the underlying price that the table is keyed on.
+    pub underlying_key: UnderlyingKey,
+    /// Underlying ticks between bid and ask, assumed when only one side is published.
+    pub spread_ticks: i64,
+    /// How index levels off the underlying tick ladder are rounded.
+    pub index_rounding: Snap,
+    /// Ladder of the table keys, or None if keyed on the tick ladder of the underlying.
+    pub ladder: Option<TickLadder>,
+}
+
+impl QuoteConvention {
+    /// The convention of all tables on output: keyed on the underlying bid, with index levels
+    /// rounded down to the tick.
+    pub const NORMALIZED: QuoteConvention = QuoteConvention {
+        underlying_key: UnderlyingKey::Bid,
+        spread_ticks: 1,
+        index_rounding: Snap::Down,
+        ladder: None,
+    };
+
+    /// Returns the convention of the issuer of given DW.
+    ///
+    /// DW13 SET50 call tables are keyed on the SET50 futures ask, in [SET50_HALF_POINT], and 2.00
+    /// points above the bid, as in the scraper before the conventions. The other tables, including
+    /// DW06 ones, are keyed as published.
+    ///
+    /// # Arguments
+    ///
+    /// * `dw_info` - DW info.
+    pub fn for_dw ( dw_info: &DWInfo ) -> QuoteConvention {
+        match dw_info.broker_id {
+            13 if dw_info.is_set50 ( ) && dw_info.side == DWSide::C => QuoteConvention {
+                underlying_key: UnderlyingKey::Ask,
+                spread_ticks: 4,
+                ladder: Some ( SET50_HALF_POINT ),
+                ..QuoteConvention::NORMALIZED
+            },
+            _ => QuoteConvention::NORMALIZED,
+        }
+    }
+
+    /// Returns the underlying bid of given key of the table.
+    ///
+    /// # Arguments
+    ///
+    /// * `ladder` - Tick ladder of the underlying, used if the convention has no ladder of its own.
+    /// * `key` - Underlying price that the table is keyed on.
+    pub fn to_underlying_bid ( &self, ladder: &TickLadder, key: Price ) -> Price {
+        let ladder = self.ladder.as_ref ( ).unwrap_or ( ladder );
+        let key = ladder.snap ( key, self.index_rounding );
+        let ticks = match self.underlying_key {
+            UnderlyingKey::Bid => 0,
+            UnderlyingKey::Ask => self.spread_ticks,
+            UnderlyingKey::Mid => self.spread_ticks / 2,
+        };
+        ladder.step ( key, -ticks ).unwrap_or ( key )
+    }
+
+    /// Returns the table normalized from this convention to [QuoteConvention::NORMALIZED].
+    ///
+    /// # Arguments
+    ///
+    /// * `dw_info` - DW info of the table.
+    /// * `table` - Table in this convention.
+    pub fn normalize_price_table ( &self, dw_info: &DWInfo, table: PriceTable ) -> PriceTable {
+        let ladder = dw_info.underlying ( ).tick_ladder;
+        let mut normalized = PriceTable::new (
+            table.dw_symbol,
+            table.publish_time,
+            table.columns.into_iter ( )
+                .map ( |column| PriceColumn::new (
+                    column.date,
+                    column.quotes.into_iter ( )
+                        .map ( |mut quote| {
+                            let key = quote.underlying_bid;
+                            quote.underlying_bid = self.to_underlying_bid ( &ladder, key );
+                            if self.underlying_key == UnderlyingKey::Ask && quote.underlying_ask.is_none ( ) {
+                                quote.underlying_ask = Some ( key );
+                            }
+                            quote
+                        } )
+                        .collect ( )
+                ) )
+                .collect ( )
+        );
+        normalized.underlying_display = table.underlying_display;
+        normalized.underlying_currency = dw_info.underlying ( ).currency;
+        normalized.fx_rate = table.fx_rate;
+        normalized
+    }
+
+    /// Returns the map of underlying price to DW prices normalized from this convention to
+    /// [QuoteConvention::NORMALIZED].
+    ///
+    /// If several keys are normalized to the same underlying bid, the lowest key is kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `dw_info` - DW info of the table.
+    /// * `table` - Map of underlying price, in i32 format based on [crate::underlying::Underlying::price_digits], to DW prices in this convention.
+    pub fn normalize_underlying_dw_price_table ( &self, dw_info: &DWInfo, table: HashMap<i32, Vec<f32>> ) -> HashMap<i32, Vec<f32>> {
+        if *self == QuoteConvention::NORMALIZED {
+            return table;
+        }
+        let underlying = dw_info.underlying ( );
+        let ( ladder, price_digits ) = ( underlying.tick_ladder, underlying.price_digits as usize );
+        let mut normalized = HashMap::<i32, Vec<f32>>::new ( );
+        for ( key, dws ) in table.into_iter ( ).collect::<BTreeMap<i32, Vec<f32>>> ( ) {
+            normalized.entry (
+                self.to_underlying_bid ( &ladder, Price::from_int_price ( key, price_digits ) )
+                    .to_int_price ( price_digits )
+            ).or_insert ( dws );
+        }
+        normalized
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use crate::price_table::Quote;
+    use crate::ticks::SET_EQUITY;
+    use crate::testing::p;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_for_dw ( ) {
+        let convention = | symbol: &str | QuoteConvention::for_dw ( &DWInfo::from_str ( symbol ).unwrap ( ) ).underlying_key;
+        assert_eq ! ( convention ( "S5013C2109A" ), UnderlyingKey::Ask );
+        // DW06 SET50 calls are keyed as published
+        assert_eq ! ( convention ( "S5006C2109A" ), UnderlyingKey::Bid );
+        assert_eq ! ( convention ( "S5013P2109A" ), UnderlyingKey::Bid );
+        assert_eq ! ( convention ( "S5028C2012D" ), UnderlyingKey::Bid );
+        assert_eq ! ( convention ( "DW13C0000A" ), UnderlyingKey::Bid );
+        assert_eq ! ( convention ( "SET5013C2306I" ), UnderlyingKey::Ask );
+        // calls on the other indices are keyed as published
+        assert_eq ! ( convention ( "HSI06C2408F" ), UnderlyingKey::Bid );
+        assert_eq ! ( convention ( "SPX13C2103A" ), UnderlyingKey::Bid );
+    }
+
+    #[test]
+    fn test_to_underlying_bid ( ) {
+        let ask = QuoteConvention { underlying_key: UnderlyingKey::Ask, ..QuoteConvention::NORMALIZED };
+        let mid = QuoteConvention { underlying_key: UnderlyingKey::Mid, spread_ticks: 2, ..QuoteConvention::NORMALIZED };
+        let ladder = TickLadder::for_underlying ( "S50" );
+
+        assert_eq ! ( QuoteConvention::NORMALIZED.to_underlying_bid ( &ladder, p ( "904.605" ) ), p ( "904.60" ) );
+        assert_eq ! ( ask.to_underlying_bid ( &ladder, p ( "904.60" ) ), p ( "904.59" ) );
+        assert_eq ! ( mid.to_underlying_bid ( &ladder, p ( "904.60" ) ), p ( "904.59" ) );
+        assert_eq ! ( ask.to_underlying_bid ( &SET_EQUITY, p ( "2.00" ) ), p ( "1.99" ) );
+
+        // the ladder of the convention over the one of the underlying
+        let dw13 = QuoteConvention::for_dw ( &DWInfo::from_str ( "S5013C2109A" ).unwrap ( ) );
+        assert_eq ! ( dw13.to_underlying_bid ( &ladder, p ( "920.50" ) ), p ( "918.50" ) );
+        assert_eq ! ( dw13.to_underlying_bid ( &ladder, p ( "920.60" ) ), p ( "918.50" ) );
+    }
+
+    #[test]
+    fn test_normalize ( ) {
+        let dw_info = DWInfo::from_str ( "S5013C2109A" ).unwrap ( );
+        let convention = QuoteConvention::for_dw ( &dw_info );
+        let date = NaiveDate::from_ymd_opt ( 2021, 3, 1 ).unwrap ( );
+
+        let table = convention.normalize_price_table ( &dw_info, PriceTable::new (
+            dw_info.symbol.clone ( ),
+            None,
+            vec ! [ PriceColumn::new ( date, vec ! [ Quote { underlying_bid: p ( "904.50" ), underlying_ask: None, bid: p ( "0.55" ), ask: None } ] ) ]
+        ) );
+        assert_eq ! ( table.columns [ 0 ].quotes [ 0 ].underlying_bid, p ( "902.50" ) );
+        assert_eq ! ( table.columns [ 0 ].quotes [ 0 ].underlying_ask, Some ( p ( "904.50" ) ) );
+
+        let map = convention.normalize_underlying_dw_price_table ( &dw_info, vec ! [ ( 90450, vec ! [ 0.55 ] ), ( 90500, vec ! [ 0.56 ] ) ].into_iter ( ).collect ( ) );
+        assert_eq ! ( map.get ( &90250 ), Some ( &vec ! [ 0.55 ] ) );
+        assert_eq ! ( map.get ( &90300 ), Some ( &vec ! [ 0.56 ] ) );
+        assert_eq ! ( map.len ( ), 2 );
+    }
+}
@@ -1,16 +1,20 @@
 
 use crate::{
     instrument::{
-        to_lower_adjacent_price,
-        to_int_price,
         dw::{
             DWInfo,
-            DWSide,
             DWPriceTable,
             Error,
         },
     },
-    RE_S50,
+    price::Price,
+    price_table::{
+        PriceColumn,
+        PriceTable,
+        Quote,
+    },
+    calendar::TradingCalendar,
+    convention::QuoteConvention,
     DEFAULT_PRICE_DIGIT,
 };
 use async_trait::async_trait;
@@ -25,21 +29,11 @@ use log::debug;
 #[cfg(test)]
 use env_logger;
 
-#[cfg(not(test))]
 use crate::get_latest_working_date_time;
 
-#[cfg(test)]
-use std::str::FromStr;
-
-#[cfg(test)]
-fn get_latest_working_date_time ( ) -> DateTime<Local> {
-    DateTime::from_str ("2024-07-04T12:00:00-00:00").unwrap ( )
-}
-
-#[cfg(test)]
 use chrono::{
-    DateTime,
-    Local,
+    Datelike,
+    NaiveDate,
 };
 
 #[cfg(test)]
@@ -242,7 +236,10 @@ macro_rules ! client_get {
 }
 
 use std::{
-    collections::HashMap,
+    collections::{
+        BTreeMap,
+        HashMap,
+    },
 };
 
 #[cfg(test)]
@@ -265,6 +262,71 @@ macro_rules! DW_PRICE_TABLE_URL {
     };
 }
 
+impl DW06 {
+    /// From given dw_info, fetch the DW price calculator centered on the given underlying price,
+    /// and returns the price table of T1..T5, normalized by [QuoteConvention::for_dw].
+    /// 
+    /// If Not found data, return Err
+    /// 
+    /// # Arguments
+    /// 
+    /// * `dw_info` - DW info.
+    /// * `underlying_price` - Underlying price in i32 format, based on [DEFAULT_PRICE_DIGIT], as the center of the table. If None, the issuer centers the table on the current underlying price.
+    pub async fn get_price_table_centered_at ( dw_info: &DWInfo, underlying_price: Option<i32> ) -> Result<PriceTable, Error> {
+        let now = get_latest_working_date_time ( );
+        let today = NaiveDate::from_ymd_opt ( now.year ( ), now.month ( ), now.day ( ) )
+            .expect ( "Failed to get the date of the latest working date/time" );
+
+        let url = match underlying_price {
+            Some ( price ) => DW_PRICE_TABLE_URL ! ( dw_info.symbol, to_url_price ( price ) ),
+            None => DW_PRICE_TABLE_URL ! ( dw_info.symbol ),
+        };
+
+        let table: JsonData = client_get(url.as_str()).await;
+
+        match table.data {
+            Some ( data ) => Ok ( QuoteConvention::for_dw ( dw_info ).normalize_price_table ( dw_info, data.to_price_table ( today ) ) ),
+            None => Err ( Error::DataNotFound { symbol: dw_info.symbol.clone(), info: Some(format!("Not found data in GetCalculator: ResponseCode={}", table.response_code)) } ),
+        }
+    }
+
+    /// From given dw_info, fetch the DW price calculator centered on each of the given underlying
+    /// prices, and returns one price table stitched from all of them.
+    /// 
+    /// If Not found data in any request, return Err
+    /// 
+    /// # Arguments
+    /// 
+    /// * `dw_info` - DW info.
+    /// * `underlying_prices` - Underlying prices in i32 format, based on [DEFAULT_PRICE_DIGIT], as the centers of the tables. If empty, the table centered on the current underlying price is returned.
+    pub async fn get_stitched_price_table ( dw_info: &DWInfo, underlying_prices: &[i32] ) -> Result<PriceTable, Error> {
+        if underlying_prices.is_empty ( ) {
+            return DW06::get_price_table_centered_at ( dw_info, None ).await;
+        }
+
+        let mut stitched: Option<PriceTable> = None;
+        for price in underlying_prices {
+            let table = DW06::get_price_table_centered_at ( dw_info, Some ( *price ) ).await?;
+            match stitched.as_mut ( ) {
+                Some ( stitched ) => stitched.merge ( table ),
+                None => stitched = Some ( table ),
+            }
+        }
+
+        Ok ( stitched.unwrap ( ) )
+    }
+}
+
+/// Returns the underlying price in the format of underlyCalPrice parameter, e.g. 1745000 to "17450.00".
+/// 
+/// # Arguments
+/// 
+/// * `price` - Underlying price in i32 format, based on [DEFAULT_PRICE_DIGIT].
+fn to_url_price ( price: i32 ) -> String {
+    let divisor = 10i32.pow ( DEFAULT_PRICE_DIGIT as u32 );
+    format ! ( "{}.{:0width$}", price / divisor, price % divisor, width = DEFAULT_PRICE_DIGIT )
+}
+
 #[async_trait(?Send)]
 impl DWPriceTable for DW06 {
     type UnderlyingType = i32;
@@ -273,8 +335,6 @@ impl DWPriceTable for DW06 {
     //type TableResult = Result<HashMap<i32, Vec<f32>>, ( )>;
 
     async fn get_underlying_dw_price_table ( dw_info: &DWInfo ) -> Result<HashMap<i32, Vec<f32>>, Error> {
-        use log::debug;
-
         #[cfg(test)]
         {
             let mut states = TEST_STATE
@@ -312,20 +372,26 @@ impl DWPriceTable for DW06 {
             return Ok ( u_dw_price_map );
         }
         let table = table.unwrap().dw_price_matrix_table;
+        let price_digits = dw_info.underlying ( ).price_digits as usize;
         for row in table.bid_rows.into_iter ( ) {
             
-            let dws = vec![
-                row.bid_t1,
-                row.bid_t2,
-                row.bid_t3,
-                row.bid_t4,
-                row.bid_t5,
-            ];
+            let dws = row.prices ( )
+                .iter ( )
+                .map ( |dw| dw.map ( |dw| dw.to_f32 ( ) ).unwrap_or ( 0f32 ) )
+                .collect ( );
             
-            u_dw_price_map.insert ( ( ( row.underly_bid_offer * 100.0 ).round ( ) ) as i32, dws );
+            u_dw_price_map.insert ( row.underly_bid_offer.to_int_price ( price_digits ), dws );
         }
          
-        Ok ( u_dw_price_map )
+        Ok ( QuoteConvention::for_dw ( dw_info ).normalize_underlying_dw_price_table ( dw_info, u_dw_price_map ) )
+    }
+
+    /// From given dw_info, fetch the DW price calculator, and returns the price table of T1..T5
+    /// with both bid and offer sides, and the underlying display name.
+    /// 
+    /// If Not found data, return Err
+    async fn get_price_table ( dw_info: &DWInfo ) -> Result<PriceTable, Error> {
+        DW06::get_price_table_centered_at ( dw_info, None ).await
     }
 
     /*
@@ -467,26 +533,105 @@ struct Data {
     dw_price_matrix_table: DwPriceMatrixTable,
 }
 
+impl Data {
+    /// Returns the price table of T1..T5, with the offer side matched by the underlying price.
+    /// 
+    /// Offer rows at an underlying price without a bid row are kept as ask-only quotes, with
+    /// [Price::ZERO] bid, the same as a null bid.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `today` - The date of T1.
+    fn to_price_table ( &self, today: NaiveDate ) -> PriceTable {
+        let table = &self.dw_price_matrix_table;
+
+        let mut rows: BTreeMap<Price, ( DailyPrices, DailyPrices )> = BTreeMap::new ( );
+        for row in table.bid_rows.iter ( ) {
+            rows.entry ( row.underly_bid_offer ).or_default ( ).0 = row.prices ( );
+        }
+        for row in table.offer_rows.iter ( ) {
+            rows.entry ( row.underly_bid_offer ).or_default ( ).1 = row.prices ( );
+        }
+
+        let columns = t_dates ( today )
+            .into_iter ( )
+            .enumerate ( )
+            .map ( |(idx, date)| PriceColumn::new (
+                date,
+                rows.iter ( )
+                    .filter_map ( |( underlying, ( bids, offers ) )| {
+                        let bid = bids [ idx ];
+                        let ask = offers [ idx ];
+                        if bid.is_none ( ) && ask.is_none ( ) {
+                            return None;
+                        }
+                        Some ( Quote {
+                            underlying_bid: *underlying,
+                            underlying_ask: None,
+                            bid: bid.unwrap_or ( Price::ZERO ),
+                            ask,
+                        } )
+                    } )
+                    .collect ( )
+            ) )
+            .collect ( );
+
+        let mut price_table = PriceTable::new ( self.dw_code.as_str ( ).into ( ), None, columns );
+        price_table.underlying_display = Some ( self.underly_display.as_str ( ).into ( ) );
+        price_table
+    }
+}
+
 #[derive(Deserialize, Debug)]
 struct DwPriceMatrixTable {
     #[serde(rename = "BidRows")]
     bid_rows: Vec<UnderlyingDwRow>,
+    #[serde(rename = "OfferRows", default)]
+    offer_rows: Vec<UnderlyingDwRow>,
+}
+
+/// Returns the dates of T1..T5: [today] as T1, and the next trading days of the SET calendar.
+/// 
+/// GetCalculator has no day labels, only the T1..T5 columns. See [TradingCalendar::set].
+/// 
+/// # Arguments
+/// 
+/// * `today` - The date of T1.
+fn t_dates ( today: NaiveDate ) -> Vec<NaiveDate> {
+    let calendar = TradingCalendar::set ( );
+    let mut dates = vec ! [ today ];
+    while dates.len ( ) < 5 {
+        let next = calendar.next_trading_day ( dates [ dates.len ( ) - 1 ] );
+        dates.push ( next );
+    }
+    dates
 }
 
+/// DW prices of T1..T5 in one side. None if not quoted.
+type DailyPrices = [Option<Price>; 5];
+
+/// Row of DW prices of T1..T5 at one underlying price, in either bid or offer side.
 #[derive(Deserialize, Debug)]
 struct UnderlyingDwRow {
     #[serde(rename = "UnderlyBidOffer")]
-    underly_bid_offer: f32,
-    #[serde(rename = "BidT1", deserialize_with = "deserialize_null_or_0f32")]
-    bid_t1: f32,
-    #[serde(rename = "BidT2", deserialize_with = "deserialize_null_or_0f32")]
-    bid_t2: f32,
-    #[serde(rename = "BidT3", deserialize_with = "deserialize_null_or_0f32")]
-    bid_t3: f32,
-    #[serde(rename = "BidT4", deserialize_with = "deserialize_null_or_0f32")]
-    bid_t4: f32,
-    #[serde(rename = "BidT5", deserialize_with = "deserialize_null_or_0f32")]
-    bid_t5: f32,
+    underly_bid_offer: Price,
+    #[serde(rename = "BidT1", alias = "OfferT1")]
+    t1: Option<Price>,
+    #[serde(rename = "BidT2", alias = "OfferT2")]
+    t2: Option<Price>,
+    #[serde(rename = "BidT3", alias = "OfferT3")]
+    t3: Option<Price>,
+    #[serde(rename = "BidT4", alias = "OfferT4")]
+    t4: Option<Price>,
+    #[serde(rename = "BidT5", alias = "OfferT5")]
+    t5: Option<Price>,
+}
+
+impl UnderlyingDwRow {
+    /// Returns DW prices of T1..T5. None if the price is "null".
+    fn prices ( &self ) -> DailyPrices {
+        [ self.t1, self.t2, self.t3, self.t4, self.t5 ]
+    }
 }
 
 /// Deserialize value from json into Some<Data>. If the original data is "null", then returns None
@@ -498,15 +643,6 @@ where
     Ok(k)
 }
 
-/// Deserialize value from json into f32. If the original data is "null", then returns 0.0f32
-fn deserialize_null_or_0f32<'de, D>(de: D) -> Result<f32, D::Error>
-where
-    D: Deserializer<'de>,
-{
-    let k = Option::<f32>::deserialize(de)?;
-    Ok(k.unwrap_or(0f32))
-}
-
 #[cfg(test)]
 use crate::testing::{gen_mock, test_count, test_last_dw_symbol};
 #[cfg(test)]
@@ -515,6 +651,7 @@ gen_mock!(dw06);
 #[cfg(test)]
 pub mod dw06_tests {
     use super::*;
+    use std::str::FromStr;
     
     use std::sync::Once;
     
@@ -526,6 +663,9 @@ pub mod dw06_tests {
                 let _ = env_logger::try_init ( );
             } );
         }
+        crate::clock::set_thread_clock_source ( Some ( std::sync::Arc::new ( crate::clock::FixedClock::at_bangkok (
+            chrono::NaiveDate::from_ymd_opt ( 2024, 7, 4 ).unwrap ( ).and_hms_opt ( 12, 0, 0 ).unwrap ( )
+        ) ) ) );
     }
     
     #[tokio::test]
@@ -536,10 +676,10 @@ pub mod dw06_tests {
             // let mut result = HTML_MAP
             //     .lock ( )
             //     .unwrap ( );
-            result.insert ( "https://dw06.kkpfg.com/DW/GetCalculator?lang=en&dwCode=DW06C2408F&underlyCalPrice=0".to_owned ( ).into_boxed_str ( ), target_json!("CALL").to_owned ( ) );
+            result.insert ( "https://dw06.kkpfg.com/DW/GetCalculator?lang=en&dwCode=HSI06C2408F&underlyCalPrice=0".to_owned ( ).into_boxed_str ( ), target_json!("CALL").to_owned ( ) );
         } );
         
-        let out = DW06::get_underlying_dw_price_table(& DWInfo::from_str ( "DW06C2408F" ).unwrap ( ) )
+        let out = DW06::get_underlying_dw_price_table(& DWInfo::from_str ( "HSI06C2408F" ).unwrap ( ) )
             .await;
         
         assert ! ( out.is_ok ( ) );
@@ -548,7 +688,7 @@ pub mod dw06_tests {
 
         // check details
         assert_eq ! ( table.keys ( ).len ( ), 41 );
-        for underlying_key in ( 1645000i32..=1745000i32 ).step_by ( 2500 ) {
+        for underlying_key in ( 16450i32..=17450i32 ).step_by ( 25 ) {
             assert ! ( table.contains_key ( &underlying_key ), "Not found underlying [{}] in table", underlying_key );
             
             let dw_list = table.get ( &underlying_key );
@@ -559,46 +699,46 @@ pub mod dw06_tests {
             // debug!("{:?}", dw_list);
 
             match dw_list [ 0 ] {
-                v if v == 0.01 => assert ! ( underlying_key >= 1645000 && underlying_key <= 1655000 ),
-                v if v == 0.02 => assert ! ( underlying_key >= 1657500 && underlying_key <= 1675000 ),
-                v if v == 0.03 => assert ! ( underlying_key >= 1677500 && underlying_key <= 1687500 ),
-                v if v == 0.04 => assert ! ( underlying_key >= 1690000 && underlying_key <= 1700000 ),
-                v if v == 0.05 => assert ! ( underlying_key >= 1702500 && underlying_key <= 1707500 ),
-                v if v == 0.06 => assert ! ( underlying_key >= 1710000 && underlying_key <= 1717500 ),
-                v if v == 0.07 => assert ! ( underlying_key >= 1720000 && underlying_key <= 1725000 ),
-                v if v == 0.08 => assert ! ( underlying_key >= 1727500 && underlying_key <= 1730000 ),
-                v if v == 0.09 => assert ! ( underlying_key >= 1732500 && underlying_key <= 1737500 ),
-                v if v == 0.10 => assert ! ( underlying_key >= 1740000 && underlying_key <= 1742500 ),
-                v if v == 0.11 => assert ! ( underlying_key >= 1745000 && underlying_key <= 1745000 ),
+                v if v == 0.01 => assert ! ( underlying_key >= 16450 && underlying_key <= 16550 ),
+                v if v == 0.02 => assert ! ( underlying_key >= 16575 && underlying_key <= 16750 ),
+                v if v == 0.03 => assert ! ( underlying_key >= 16775 && underlying_key <= 16875 ),
+                v if v == 0.04 => assert ! ( underlying_key >= 16900 && underlying_key <= 17000 ),
+                v if v == 0.05 => assert ! ( underlying_key >= 17025 && underlying_key <= 17075 ),
+                v if v == 0.06 => assert ! ( underlying_key >= 17100 && underlying_key <= 17175 ),
+                v if v == 0.07 => assert ! ( underlying_key >= 17200 && underlying_key <= 17250 ),
+                v if v == 0.08 => assert ! ( underlying_key >= 17275 && underlying_key <= 17300 ),
+                v if v == 0.09 => assert ! ( underlying_key >= 17325 && underlying_key <= 17375 ),
+                v if v == 0.10 => assert ! ( underlying_key >= 17400 && underlying_key <= 17425 ),
+                v if v == 0.11 => assert ! ( underlying_key >= 17450 && underlying_key <= 17450 ),
                 _ => panic ! ( )
             }
 
             match dw_list [ 1 ] {                
-                v if v == 0.01 => assert ! ( underlying_key >= 1645000 && underlying_key <= 1662500 ),
-                v if v == 0.02 => assert ! ( underlying_key >= 1665000 && underlying_key <= 1682500 ),
-                v if v == 0.03 => assert ! ( underlying_key >= 1685000 && underlying_key <= 1695000 ),
-                v if v == 0.04 => assert ! ( underlying_key >= 1697500 && underlying_key <= 1705000 ),
-                v if v == 0.05 => assert ! ( underlying_key >= 1707500 && underlying_key <= 1715000 ),
-                v if v == 0.06 => assert ! ( underlying_key >= 1717500 && underlying_key <= 1722500 ),
-                v if v == 0.07 => assert ! ( underlying_key >= 1725000 && underlying_key <= 1730000 ),
-                v if v == 0.08 => assert ! ( underlying_key >= 1732500 && underlying_key <= 1737500 ),
-                v if v == 0.09 => assert ! ( underlying_key >= 1740000 && underlying_key <= 1742500 ),
-                v if v == 0.10 => assert ! ( underlying_key >= 1745000 && underlying_key <= 1745000 ),
+                v if v == 0.01 => assert ! ( underlying_key >= 16450 && underlying_key <= 16625 ),
+                v if v == 0.02 => assert ! ( underlying_key >= 16650 && underlying_key <= 16825 ),
+                v if v == 0.03 => assert ! ( underlying_key >= 16850 && underlying_key <= 16950 ),
+                v if v == 0.04 => assert ! ( underlying_key >= 16975 && underlying_key <= 17050 ),
+                v if v == 0.05 => assert ! ( underlying_key >= 17075 && underlying_key <= 17150 ),
+                v if v == 0.06 => assert ! ( underlying_key >= 17175 && underlying_key <= 17225 ),
+                v if v == 0.07 => assert ! ( underlying_key >= 17250 && underlying_key <= 17300 ),
+                v if v == 0.08 => assert ! ( underlying_key >= 17325 && underlying_key <= 17375 ),
+                v if v == 0.09 => assert ! ( underlying_key >= 17400 && underlying_key <= 17425 ),
+                v if v == 0.10 => assert ! ( underlying_key >= 17450 && underlying_key <= 17450 ),
                 _ => panic ! ( )
             }
 
             match dw_list [ 2 ] {
-                v if v == 0.00 => assert ! ( underlying_key >= 1645000 && underlying_key <= 1745000 ),
+                v if v == 0.00 => assert ! ( underlying_key >= 16450 && underlying_key <= 17450 ),
                 _ => panic ! ( )
             }
 
             match dw_list [ 3 ] {
-                v if v == 0.00 => assert ! ( underlying_key >= 1645000 && underlying_key <= 1745000 ),
+                v if v == 0.00 => assert ! ( underlying_key >= 16450 && underlying_key <= 17450 ),
                 _ => panic ! ( )
             }
             
             match dw_list [ 4 ] {
-                v if v == 0.00 => assert ! ( underlying_key >= 1645000 && underlying_key <= 1745000 ),
+                v if v == 0.00 => assert ! ( underlying_key >= 16450 && underlying_key <= 17450 ),
                 _ => panic ! ( )
             }
         }
@@ -694,4 +834,130 @@ pub mod dw06_tests {
         // check details
         assert_eq ! ( table.keys ( ).len ( ), 0 );
     }
-}
\ No newline at end of file
+
+    const PRICE_TABLE_JSON: &str = r#"{
+        "ResponseCode": 0,
+        "Data": {
+            "DWCode": "HSI06C2408F",
+            "UnderlyDisplay": "HSI",
+            "DwPriceMatrixTable": {
+                "BidRows": [
+                    { "UnderlyBidOffer": 17475.0, "BidT1": 0.03, "BidT2": 0.03, "BidT3": 0.02, "BidT4": 0.02, "BidT5": null },
+                    { "UnderlyBidOffer": 17450.0, "BidT1": 0.02, "BidT2": 0.02, "BidT3": 0.02, "BidT4": null, "BidT5": null }
+                ],
+                "OfferRows": [
+                    { "UnderlyBidOffer": 17450.0, "OfferT1": 0.03, "OfferT2": 0.03, "OfferT3": 0.03, "OfferT4": 0.03, "OfferT5": null },
+                    { "UnderlyBidOffer": 17475.0, "OfferT1": 0.04, "OfferT2": 0.04, "OfferT3": 0.03, "OfferT4": 0.03, "OfferT5": null }
+                ]
+            }
+        }
+    }"#;
+
+    #[tokio::test]
+    pub async fn test_get_price_table_call ( ) {
+        setup ( );
+        HTML_MAP.with ( |html_map| {
+            let mut result = html_map.borrow_mut ( );
+            result.insert ( "https://dw06.kkpfg.com/DW/GetCalculator?lang=en&dwCode=HSI06C2408F&underlyCalPrice=0".to_owned ( ).into_boxed_str ( ), PRICE_TABLE_JSON.to_owned ( ) );
+        } );
+
+        let out = DW06::get_price_table ( & DWInfo::from_str ( "HSI06C2408F" ).unwrap ( ) )
+            .await;
+
+        assert ! ( out.is_ok ( ) );
+
+        let table = out.unwrap ( );
+
+        assert_eq ! ( &*table.dw_symbol, "HSI06C2408F" );
+        assert_eq ! ( table.underlying_display.as_deref ( ), Some ( "HSI" ) );
+
+        // T1 on the mocked today, then the next trading days, skipping the weekend
+        assert_eq ! ( table.dates ( ), vec ! [
+            NaiveDate::from_ymd_opt ( 2024, 7, 4 ).unwrap ( ),
+            NaiveDate::from_ymd_opt ( 2024, 7, 5 ).unwrap ( ),
+            NaiveDate::from_ymd_opt ( 2024, 7, 8 ).unwrap ( ),
+            NaiveDate::from_ymd_opt ( 2024, 7, 9 ).unwrap ( ),
+            NaiveDate::from_ymd_opt ( 2024, 7, 10 ).unwrap ( ),
+        ] );
+
+        let t1 = &table.columns [ 0 ].quotes;
+        assert_eq ! ( t1.len ( ), 2 );
+        assert_eq ! ( t1 [ 0 ], Quote { underlying_bid: Price::new ( 1745000, 2 ), underlying_ask: None, bid: Price::new ( 2, 2 ), ask: Some ( Price::new ( 3, 2 ) ) } );
+        assert_eq ! ( t1 [ 1 ], Quote { underlying_bid: Price::new ( 1747500, 2 ), underlying_ask: None, bid: Price::new ( 3, 2 ), ask: Some ( Price::new ( 4, 2 ) ) } );
+
+        // bid is null but offer is quoted
+        let t4 = &table.columns [ 3 ].quotes;
+        assert_eq ! ( t4.len ( ), 2 );
+        assert_eq ! ( t4 [ 0 ], Quote { underlying_bid: Price::new ( 1745000, 2 ), underlying_ask: None, bid: Price::ZERO, ask: Some ( Price::new ( 3, 2 ) ) } );
+
+        // neither side is quoted
+        assert ! ( table.columns [ 4 ].quotes.is_empty ( ) );
+    }
+
+    #[tokio::test]
+    pub async fn test_get_price_table_with_offer_rows_not_in_bid_rows ( ) {
+        setup ( );
+        HTML_MAP.with ( |html_map| {
+            let mut result = html_map.borrow_mut ( );
+            result.insert (
+                "https://dw06.kkpfg.com/DW/GetCalculator?lang=en&dwCode=HSI06C2408F&underlyCalPrice=0".to_owned ( ).into_boxed_str ( ),
+                PRICE_TABLE_JSON.replace ( r#"{ "UnderlyBidOffer": 17475.0, "OfferT1""#, r#"{ "UnderlyBidOffer": 17500.0, "OfferT1""# )
+            );
+        } );
+
+        let table = DW06::get_price_table ( & DWInfo::from_str ( "HSI06C2408F" ).unwrap ( ) )
+            .await
+            .unwrap ( );
+
+        let t1 = &table.columns [ 0 ].quotes;
+        assert_eq ! ( t1, &vec ! [
+            Quote { underlying_bid: Price::new ( 1745000, 2 ), underlying_ask: None, bid: Price::new ( 2, 2 ), ask: Some ( Price::new ( 3, 2 ) ) },
+            // bid only
+            Quote { underlying_bid: Price::new ( 1747500, 2 ), underlying_ask: None, bid: Price::new ( 3, 2 ), ask: None },
+            // offer only
+            Quote { underlying_bid: Price::new ( 1750000, 2 ), underlying_ask: None, bid: Price::ZERO, ask: Some ( Price::new ( 4, 2 ) ) },
+        ] );
+    }
+
+    #[test]
+    fn test_to_url_price ( ) {
+        assert_eq ! ( to_url_price ( 1745000 ), "17450.00" );
+        assert_eq ! ( to_url_price ( 90510 ), "905.10" );
+        assert_eq ! ( to_url_price ( 5 ), "0.05" );
+    }
+
+    #[tokio::test]
+    pub async fn test_get_stitched_price_table ( ) {
+        setup ( );
+        HTML_MAP.with ( |html_map| {
+            let mut result = html_map.borrow_mut ( );
+            result.insert ( "https://dw06.kkpfg.com/DW/GetCalculator?lang=en&dwCode=HSI06C2408F&underlyCalPrice=17450.00".to_owned ( ).into_boxed_str ( ), PRICE_TABLE_JSON.to_owned ( ) );
+            result.insert (
+                "https://dw06.kkpfg.com/DW/GetCalculator?lang=en&dwCode=HSI06C2408F&underlyCalPrice=18000.00".to_owned ( ).into_boxed_str ( ),
+                PRICE_TABLE_JSON
+                    .replace ( "17475.0", "18025.0" )
+                    .replace ( "17450.0", "18000.0" )
+            );
+            result.insert ( "https://dw06.kkpfg.com/DW/GetCalculator?lang=en&dwCode=HSI06C2408F&underlyCalPrice=19000.00".to_owned ( ).into_boxed_str ( ), r#"{ "ResponseCode": 404, "Data": null }"#.to_owned ( ) );
+        } );
+
+        let out = DW06::get_stitched_price_table ( & DWInfo::from_str ( "HSI06C2408F" ).unwrap ( ), &[ 1745000, 1800000 ] )
+            .await;
+
+        assert ! ( out.is_ok ( ) );
+
+        let table = out.unwrap ( );
+
+        assert_eq ! ( table.columns.len ( ), 5 );
+        assert_eq ! (
+            table.columns [ 0 ].quotes.iter ( ).map ( |q| q.underlying_bid.to_int_price ( DEFAULT_PRICE_DIGIT ) ).collect::<Vec<i32>> ( ),
+            vec ! [ 1745000, 1747500, 1800000, 1802500 ]
+        );
+
+        // not found data in one of the requests
+        let out = DW06::get_stitched_price_table ( & DWInfo::from_str ( "HSI06C2408F" ).unwrap ( ), &[ 1745000, 1900000 ] )
+            .await;
+
+        assert ! ( out.is_err ( ) );
+    }
+}
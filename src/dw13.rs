@@ -1,17 +1,23 @@
 
 use crate::{
     instrument::{
-        to_lower_adjacent_price,
-        to_int_price,
         dw::{
             DWInfo,
-            DWSide,
             DWPriceTable,
             Error,
         },
     },
-    RE_S50,
-    DEFAULT_PRICE_DIGIT,
+    convention::QuoteConvention,
+    price::Price,
+    price_table::{
+        PriceColumn,
+        PriceTable,
+        Quote,
+    },
+};
+use chrono::{
+    Datelike,
+    NaiveDate,
 };
 use async_trait::async_trait;
 
@@ -22,23 +28,8 @@ use log::debug;
 #[cfg(test)]
 use env_logger;
 
-#[cfg(not(test))]
 use crate::get_latest_working_date_time;
 
-#[cfg(test)]
-use std::str::FromStr;
-
-#[cfg(test)]
-fn get_latest_working_date_time ( ) -> DateTime<Local> {
-    DateTime::from_str ("2020-12-15T12:00:00-00:00").unwrap ( )
-}
-
-#[cfg(test)]
-use chrono::{
-    DateTime,
-    Local,
-};
-
 #[cfg(test)]
 macro_rules! target_html {
     () => {
@@ -86,6 +77,13 @@ lazy_static ! {
         .dot_matches_new_line ( true )
         .build ( )
         .expect ( "Failed to create Regex pattern of the date cell." );
+    static ref RE_HEADER_CELL : Regex = RegexBuilder::new ( r#"<th[^>]*>(.*?)</th"# )
+        .case_insensitive ( true )
+        .dot_matches_new_line ( true )
+        .build ( )
+        .expect ( "Failed to create Regex pattern of the header cell." );
+    static ref RE_TAG : Regex = Regex::new ( r#"<[^>]*>"# )
+        .expect ( "Failed to create Regex pattern of the html tag." );
     static ref RE_UNDERLYING_PRICE : Regex = RegexBuilder::new ( r#">\s*([\d,]+(\.\d+)?)\s*<"# )
         .case_insensitive ( true )
         .build ( )
@@ -118,6 +116,8 @@ impl DWPriceTable for DW13 {
 
     /// From given dw_info, fetch the DW price table, and returns the extracted map of underlying price to DW price.
     ///
+    /// The underlying prices are normalized to the underlying bid, see [QuoteConvention::for_dw].
+    ///
     /// If Not found data, return Err
     async fn get_underlying_dw_price_table ( dw_info: &DWInfo ) -> Result<HashMap<i32, Vec<f32>>, Error> {
         use log::debug;
@@ -164,6 +164,7 @@ impl DWPriceTable for DW13 {
             
         if let Some ( table_match ) = RE_TABLE.find ( table.as_str ( ) ) {
             let mut u_dw_price_map = HashMap::<i32,Vec<f32>>::new ( );
+            let price_digits = dw_info.underlying ( ).price_digits as usize;
             let columns = RE_COLUMN.split ( table_match.as_str ( ) )
                 .collect::<Vec<&str>> ( );
 
@@ -196,23 +197,17 @@ impl DWPriceTable for DW13 {
                             idx_column_offset += 1;
                         } else {
                             if let Some ( price_match ) = price_capture.get ( 1 ) {
-                                if let Ok ( price ) = price_match.as_str ( ).parse ( ) {
-                                    dw_price_list.push ( price );
+                                if let Ok ( price ) = price_match.as_str ( ).parse::<Price> ( ) {
+                                    dw_price_list.push ( price.to_f32 ( ) );
                                 }
                             }
                         }
                     } else {
                         if let Some ( price_match ) = price_capture.get ( 1 ) {
-                            if let Ok ( price ) = price_match.as_str ( ).parse::<f32> ( ) {
+                            if let Ok ( price ) = price_match.as_str ( ).parse::<Price> ( ) {
                                 found_underlying_price = true;
 
-                                if dw_info.side == DWSide::C && RE_S50.is_match ( &*dw_info.symbol ) {
-                                    underlying_price = to_lower_adjacent_price (
-                                        to_int_price ( price, DEFAULT_PRICE_DIGIT )
-                                    );
-                                } else {
-                                    underlying_price = to_int_price ( price, DEFAULT_PRICE_DIGIT );
-                                }
+                                underlying_price = price.to_int_price ( price_digits );
                             }
                         }
                     }
@@ -227,7 +222,7 @@ impl DWPriceTable for DW13 {
                 debug!("ERR: u_dw_price_map.len() <= 0");
                 Err ( Error::DataNotFound { symbol: dw_info.symbol.clone(), info: Some("Found empty underlying-derivative price map".to_owned()) } )
             } else {
-                Ok ( u_dw_price_map )
+                Ok ( QuoteConvention::for_dw ( dw_info ).normalize_underlying_dw_price_table ( dw_info, u_dw_price_map ) )
             }
         } else {
             debug!("ERR: table not match RE_TABLE [{}]: {}",
@@ -238,6 +233,31 @@ impl DWPriceTable for DW13 {
         }
     }
 
+    /// From given dw_info, fetch the DW price table, and returns the table with one column per
+    /// published date.
+    ///
+    /// The underlying prices are normalized to the underlying bid, see [QuoteConvention::for_dw].
+    async fn get_price_table ( dw_info: &DWInfo ) -> Result<PriceTable, Error> {
+        let now = get_latest_working_date_time ( );
+        let today = NaiveDate::from_ymd_opt ( now.year ( ), now.month ( ), now.day ( ) )
+            .expect ( "Failed to get the date of the latest working date/time" );
+
+        let content = Client::new ( )
+            .get (
+                DW_PRICE_TABLE_URL ! ( dw_info.symbol ).as_str ( )
+            )
+            .header ( "Cookie", "CurrentLanguage=en-US" )
+            .send ( )
+            .await
+            .expect ( "Failed to connect to thaiwarrant.com" )
+            .text ( )
+            .await
+            .expect ( "Failed to get data from thaiwarrant.com in text format" );
+
+        parse_price_table ( dw_info, content.as_str ( ), today )
+            .map ( |table| QuoteConvention::for_dw ( dw_info ).normalize_price_table ( dw_info, table ) )
+    }
+
     /*
     // This case of result is found from Chrome inspect
     fn get_underlying_dw_price_table ( dw_info: &DWInfo ) -> Option<HashMap<i32, Vec<f32>>> {
@@ -357,6 +377,63 @@ impl DWPriceTable for DW13 {
     //     unimplemented!();
     // }
 }
+/// Returns the date of the date header of the table, e.g. "25 May" or "15-Dec-20".
+///
+/// # Arguments
+///
+/// * `label` - Date header, without html tags.
+/// * `today` - The latest working date, for the year of the "%d %b" format.
+fn parse_date_label ( label: &str, today: NaiveDate ) -> Option<NaiveDate> {
+    let label = label.trim ( );
+    NaiveDate::parse_from_str ( label, "%d-%b-%y" ).ok ( )
+        .or_else ( || crate::dw28::to_date_from_day_month ( label, today ) )
+}
+
+/// Returns the table parsed from given content of the DW page, with one column per date header.
+///
+/// # Arguments
+///
+/// * `dw_info` - DW info.
+/// * `content` - Content of the DW page from thaiwarrant.com
+/// * `today` - The latest working date.
+fn parse_price_table ( dw_info: &DWInfo, content: &str, today: NaiveDate ) -> Result<PriceTable, Error> {
+    let table_match = RE_TABLE.find ( content )
+        .ok_or_else ( || Error::DataNotFound { symbol: dw_info.symbol.clone(), info: Some("Failed to extract underlying-derivative price table".to_owned()) } )?;
+    let rows = RE_COLUMN.split ( table_match.as_str ( ) )
+        .collect::<Vec<&str>> ( );
+
+    // the first header is the underlying column
+    let dates = rows.get ( 2 )
+        .map ( |&row| RE_HEADER_CELL.captures_iter ( row )
+            .skip ( 1 )
+            .map ( |c| parse_date_label ( RE_TAG.replace_all ( &c [ 1 ], "" ).as_ref ( ), today ) )
+            .collect::<Vec<Option<NaiveDate>>> ( ) )
+        .unwrap_or_default ( );
+
+    let mut quotes = vec ! [ Vec::<Quote>::new ( ); dates.len ( ) ];
+    for &row in rows.iter ( ).skip ( 3 ) {
+        let mut prices = RE_UNDERLYING_PRICE.captures_iter ( row )
+            .filter_map ( |c| c [ 1 ].replace ( ',', "" ).parse::<Price> ( ).ok ( ) );
+        if let Some ( underlying ) = prices.next ( ) {
+            for ( column, bid ) in quotes.iter_mut ( ).zip ( prices ) {
+                column.push ( Quote { underlying_bid: underlying, underlying_ask: None, bid, ask: None } );
+            }
+        }
+    }
+
+    let columns = dates.into_iter ( )
+        .zip ( quotes )
+        .filter_map ( |( date, quotes )| Some ( PriceColumn::new ( date?, quotes ) ) )
+        .filter ( |column| ! column.quotes.is_empty ( ) )
+        .collect::<Vec<PriceColumn>> ( );
+
+    if columns.is_empty ( ) {
+        Err ( Error::DataNotFound { symbol: dw_info.symbol.clone(), info: Some("Found empty underlying-derivative price table".to_owned()) } )
+    } else {
+        Ok ( PriceTable::new ( dw_info.symbol.clone ( ), None, columns ) )
+    }
+}
+
 /*
 use mockall::mock;
 mock! {
@@ -388,6 +465,7 @@ gen_mock!(dw13);
 #[cfg(test)]
 pub mod dw13_tests {
     use super::*;
+    use std::str::FromStr;
     
     use std::sync::Once;
     
@@ -399,8 +477,81 @@ pub mod dw13_tests {
                 let _ = env_logger::try_init ( );
             } );
         }
+        crate::clock::set_thread_clock_source ( Some ( std::sync::Arc::new ( crate::clock::FixedClock::at_bangkok (
+            chrono::NaiveDate::from_ymd_opt ( 2020, 12, 15 ).unwrap ( ).and_hms_opt ( 12, 0, 0 ).unwrap ( )
+        ) ) ) );
     }
     
+    #[test]
+    pub fn test_parse_date_label ( ) {
+        let today = NaiveDate::from_ymd_opt ( 2020, 12, 15 ).unwrap ( );
+        assert_eq ! ( parse_date_label ( " 15-Dec-20 ", today ), NaiveDate::from_ymd_opt ( 2020, 12, 15 ) );
+        assert_eq ! ( parse_date_label ( "04 Jan", today ), NaiveDate::from_ymd_opt ( 2021, 1, 4 ) );
+        assert_eq ! ( parse_date_label ( "(ราคา Bid)", today ), None );
+    }
+
+    #[tokio::test]
+    pub async fn test_get_price_table ( ) {
+        setup ( );
+        HTML_MAP.with ( |html_map| {
+            let mut result = html_map.borrow_mut ( );
+            result.insert ( "".to_owned ( ).into_boxed_str ( ), target_html!().to_owned ( ) );
+        } );
+
+        // the day the fixture was captured, T1 of the page
+        crate::clock::set_thread_clock_source ( Some ( std::sync::Arc::new ( crate::clock::FixedClock::at_bangkok (
+            NaiveDate::from_ymd_opt ( 2023, 5, 25 ).unwrap ( ).and_hms_opt ( 12, 0, 0 ).unwrap ( )
+        ) ) ) );
+
+        // the page of the fixture, normalized to the underlying bid, see QuoteConvention::for_dw
+        let dw_info = DWInfo::from_str ( "SET5013C2306I" ).unwrap ( );
+        let table = DW13::get_price_table ( &dw_info ).await.unwrap ( );
+
+        // "25 May" to "02 Jun"
+        let dates = table.dates ( );
+        assert_eq ! ( dates.len ( ), 7 );
+        assert_eq ! ( dates [ 0 ], NaiveDate::from_ymd_opt ( 2023, 5, 25 ).unwrap ( ) );
+        assert_eq ! ( dates [ 6 ], NaiveDate::from_ymd_opt ( 2023, 6, 2 ).unwrap ( ) );
+
+        let underlying = "965.00".parse::<Price> ( ).unwrap ( );
+        let bids = table.columns.iter ( )
+            .map ( |column| column.get_quote ( underlying, crate::price_table::Match::Exact ).unwrap ( ).bid.to_string ( ) )
+            .collect::<Vec<String>> ( );
+        assert_eq ! ( bids, vec ! [ "0.64", "0.61", "0.55", "0.52", "0.49", "0.46", "0.43" ] );
+
+        // the time decay across the date columns
+        let thetas = crate::analytics::theta::get_theta ( &table, underlying );
+        assert_eq ! ( thetas.len ( ), 6 );
+        assert_eq ! ( thetas [ 0 ].ticks ( ), -3 );
+        let loss = crate::analytics::theta::get_overnight_tick_loss ( &table, underlying ).unwrap ( );
+        assert_eq ! ( loss.bid_to, "0.61".parse::<Price> ( ).unwrap ( ) );
+    }
+
+    #[tokio::test]
+    pub async fn test_get_underlying_dw_price_table_set50_call ( ) {
+        setup ( );
+        HTML_MAP.with ( |html_map| {
+            let mut result = html_map.borrow_mut ( );
+            result.insert ( DW_PRICE_TABLE_URL ! ( "SET5013C2306I" ).into_boxed_str ( ), target_html!().to_owned ( ) );
+        } );
+
+        // the page of the fixture, keyed on the SET50 futures ask in 0.50 point
+        let dw_info = DWInfo::from_str ( "SET5013C2306I" ).unwrap ( );
+        let table = DW13::get_underlying_dw_price_table ( &dw_info ).await.unwrap ( );
+        assert_eq ! ( table.len ( ), 161 );
+        assert ! ( table.keys ( ).all ( |key| key % 50 == 0 ) );
+        // normalized to the underlying bid, 2.00 points below the published 967.00
+        assert_eq ! ( table.get ( &96500 ).map ( |dw_list| dw_list [ 0 ] ), Some ( 0.64 ) );
+        // the same from the DWInfo dispatch
+        assert_eq ! ( DWInfo::get_underlying_dw_price_table ( &dw_info ).await.unwrap ( ), table );
+
+        let table = DW13::get_price_table ( &dw_info ).await.unwrap ( );
+        let quote = table.columns [ 0 ].get_quote ( Price::new ( 96500, 2 ), crate::price_table::Match::Exact ).unwrap ( );
+        assert_eq ! ( quote.underlying_ask, Some ( Price::new ( 96700, 2 ) ) );
+        assert_eq ! ( quote.bid.to_string ( ), "0.64" );
+        assert_eq ! ( DWInfo::get_price_table ( &dw_info ).await.unwrap ( ), table );
+    }
+
     #[tokio::test]
     pub async fn test_get_underlying_dw_price_table ( ) {
         setup ( );
@@ -1,40 +1,51 @@
 
 pub struct DW28;
 
-use crate::{DEFAULT_PRICE_DIGIT, instrument::{
-        to_int_price,
+use crate::{analytics::black_scholes::{
+        DWTerms,
+        MarketParams,
+    },
+    instrument::{
         dw::{
+            DWExpiry,
             DWInfo,
             DWSide,
             DWPriceTable,
             Error,
         },
+    },
+    catalogue::Catalogue,
+    convention::QuoteConvention,
+    price::Price,
+    price_table::{
+        PriceColumn,
+        PriceTable,
+        Quote,
     }};
+
+#[cfg(test)]
+use crate::price_table::Match;
+#[cfg(test)]
+use crate::{
+    analytics::greeks::get_table_greeks,
+    ticks::TickLadder,
+};
 use async_trait::async_trait;
 
+use serde::{Deserializer, Deserialize};
 use serde_json;
-use log::debug;
+use log::{debug, warn};
 
 #[cfg(test)]
 use env_logger;
 
-#[cfg(not(test))]
 use crate::get_latest_working_date_time;
 
-#[cfg(test)]
-use std::str::FromStr;
-
-#[cfg(test)]
-fn get_latest_working_date_time ( ) -> DateTime<Local> {
-    DateTime::from_str ("2020-12-23T12:00:00-00:00").unwrap ( )
-}
-
-use chrono::NaiveDate;
-
-#[cfg(test)]
 use chrono::{
-    DateTime,
-    Local,
+    Datelike,
+    Duration,
+    NaiveDate,
+    NaiveDateTime,
 };
 
 #[cfg(not(test))]
@@ -46,7 +57,13 @@ use super::reqwest_mock::HTML_MAP;
 #[cfg(test)]
 use super::reqwest_mock::Client;
 
-use std::collections::HashMap;
+use std::str::FromStr;
+
+use std::collections::{
+    BTreeMap,
+    BTreeSet,
+    HashMap,
+};
 
 use regex::{
     Regex,
@@ -56,11 +73,6 @@ use regex::{
 use lazy_static::lazy_static;
 
 lazy_static ! {
-    //static ref DW_INFO_REGEX_FORMAT : &'static str = r#"{([^}]*"security_code":"{dw_symbol}"[^}]*)}"#;
-    /*
-    static ref RE_DW_INFO : Regex = Regex::new ( r#"{([^}]*"security_code":"$"[^}]*)}"# )
-        .expect ( "Failed to create Regex pattern of the security_code data." );
-        */
     static ref RE_DW_RIC : Regex = RegexBuilder::new ( r#""ric":"([^"]+)"# )
         .case_insensitive ( true )
         .build ( )
@@ -81,14 +93,6 @@ lazy_static ! {
         .multi_line ( true )
         .build ( )
         .expect ( "Failed to create Regex pattern of the DW data." );
-    static ref RE_DW_BID_PRICE : Regex = RegexBuilder::new ( r#""bid":"?([\d\.]+)"?"# )
-        .case_insensitive ( true )
-        .build ( )
-        .expect ( "Failed to create Regex pattern of the DW bid price data." );
-    static ref RE_UNDERLYING_BID_PRICE : Regex = RegexBuilder::new ( r#""underlying_bid":"?([\d\.]+)"?"# )
-        .case_insensitive ( true )
-        .build ( )
-        .expect ( "Failed to create Regex pattern of the underlying bid price data." );
     static ref RE_DATE_KEYS : Regex = RegexBuilder::new ( r#""date_keys":(\[[^\[]+])"# )
         .case_insensitive ( true )
         .build ( )
@@ -105,18 +109,14 @@ lazy_static ! {
         .multi_line ( true )
         .build ( )
         .expect ( "Failed to create Regex pattern of the DW date data." );
-}
-
-macro_rules! DW_INFO_RE {
-    ($dw_symbol:expr) => {
-        RegexBuilder::new ( format ! ( 
-                r#"\{{([^\}}]*"security_code":"{dw_symbol}"[^\}}]*)\}}"#,
-                dw_symbol=$dw_symbol ).as_str ( )
-            )
-            .case_insensitive ( true )
-            .build ( )
-            .expect ( "Failed to create Regex pattern of the security_code data." )
-    };
+    static ref RE_RIC_DATA : Regex = RegexBuilder::new ( r#""ric_data":(\{[^\}]*\})"# )
+        .case_insensitive ( true )
+        .build ( )
+        .expect ( "Failed to create Regex pattern of the ric_data." );
+    static ref RE_HOLIDAYS : Regex = RegexBuilder::new ( r#""holidays":(\[[^\]]*\])"# )
+        .case_insensitive ( true )
+        .build ( )
+        .expect ( "Failed to create Regex pattern of the holidays." );
 }
 
 macro_rules! DW_LIST_URL {
@@ -223,55 +223,454 @@ macro_rules! target_html_compressed_spx_put {
 }
 
 impl DW28 {
+    /// Returns the RIC predicted from given DW symbol, with the DW prefix of the underlying,
+    /// e.g. "S5028C012.BK" of both "S5028C2012D" and "SET5028C2012D".
+    /// 
+    /// # Arguments
+    /// 
+    /// * `dw_info` - DW info
     pub fn get_predicted_dw_ric ( dw_info: &DWInfo ) -> String {
         format ! (
             "{underlying_part}{broker_id}{dw_type}{expiration_ymm}.BK",
-            underlying_part=dw_info.underlying_symbol,
+            underlying_part=dw_info.underlying ( ).dw_prefix,
             broker_id=dw_info.broker_id,
             dw_type=match dw_info.side {
                 DWSide::C => "C",
                 DWSide::P => "P",
                 _ => ".",
             },
-            expiration_ymm=std::str::from_utf8( &dw_info.expire_yymm [ 1..4 ] ).expect ( "Failed to convert expiration_yymm from [u8] to string" ),
+            expiration_ymm=match dw_info.expiry ( ) {
+                Some ( expiry ) => format ! ( "{}{:02}", expiry.year % 10, expiry.month ),
+                None => String::from_utf8_lossy ( &dw_info.expire_yymm [ 1..4 ] ).into_owned ( ),
+            },
         )
     }
 }
 
-#[async_trait(?Send)]
-impl DWPriceTable for DW28 {
-    type UnderlyingType = i32;
-    type DWType = f32;
+/// Returns the date of given date key in "%d %b" format, e.g. "23 Dec".
+/// 
+/// The year is taken from [base], or the next year if the date would be more than half a year
+/// before [base], as the date keys may cross the new year.
+/// 
+/// # Arguments
+/// 
+/// * `date_key` - Date key in "%d %b" format.
+/// * `base` - The date to be based on, usually the latest working date.
+pub(crate) fn to_date_from_day_month ( date_key: &str, base: NaiveDate ) -> Option<NaiveDate> {
+    let date = NaiveDate::parse_from_str ( format ! ( "{} {}", base.year ( ), date_key ).as_str ( ), "%Y %d %b" ).ok ( )?;
+    if date < base - Duration::days ( 183 ) {
+        date.with_year ( base.year ( ) + 1 )
+    } else {
+        Some ( date )
+    }
+}
 
-    // outdated
-    async fn get_underlying_dw_price_table ( dw_info: &DWInfo ) -> Result<HashMap<Self::UnderlyingType, Vec<Self::DWType>>, Error> {
-    //async fn get_underlying_dw_price_table ( dw_info: &DWInfo ) -> Result<HashMap<i32, Vec<f32>>, ()> {
-        #[cfg(test)]
-        {
-            let mut states = TEST_STATE
-                .lock()
-                .unwrap();
+/// Returns the holidays published in given content of the live matrix, in either "%Y-%m-%d" or
+/// "%d %b" format.
+/// 
+/// # Arguments
+/// 
+/// * `content` - Live matrix content from thaidw.com
+/// * `today` - The latest working date, for the year of the "%d %b" format.
+fn parse_holidays ( content: &str, today: NaiveDate ) -> Vec<NaiveDate> {
+    RE_HOLIDAYS.captures ( content )
+        .and_then ( |c| serde_json::from_str::<Vec<String>> ( c.get ( 1 )?.as_str ( ) ).ok ( ) )
+        .unwrap_or_default ( )
+        .iter ( )
+        .filter_map ( |holiday| NaiveDate::parse_from_str ( holiday, "%Y-%m-%d" ).ok ( )
+            .or_else ( || to_date_from_day_month ( holiday, today ) ) )
+        .collect ( )
+}
 
-            match states.get_mut(&thread::current().id()) {
-                None => {
-                    states
-                        .insert(
-                            thread::current().id(),
-                            TestState {
-                                count: 1,
-                                last_dw_symbol: dw_info.symbol.clone().to_string(),
-                            }
-                        );
-                },
-                Some(s) => {
-                    s.count += 1;
-                    s.last_dw_symbol = dw_info.symbol.clone().to_string();
-                }
-            }
+/// Returns the DW price at given underlying price, from the ladder of (lowest underlying, DW) pairs.
+/// 
+/// The ladder must be sorted by the underlying price. If the underlying price is out of the
+/// ladder, 0.0 is returned as not quoted.
+/// 
+/// # Arguments
+/// 
+/// * `ladder` - Pairs of the lowest underlying price and its DW price, sorted by underlying price.
+/// * `underlying` - Underlying price in i32 format.
+fn get_dw_price_from_ladder ( ladder: &[(i32, f32)], underlying: i32 ) -> f32 {
+    match ladder.binary_search_by_key ( &underlying, |&(u, _)| u ) {
+        Ok ( idx ) => ladder [ idx ].1,
+        Err ( idx ) if idx == 0 || idx == ladder.len ( ) => 0.0,
+        Err ( idx ) => ladder [ idx - 1 ].1,
+    }
+}
+
+/// One row of the DW28 live matrix.
+/// 
+/// In the compressed format, each DW bid/ask is quoted with 2 rows as the head and the tail of its
+/// underlying range, grouped by the same `head_tail_id`. In the non-compressed format, only the
+/// underlying price and the DW bid are published.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LiveMatrixRow {
+    pub head_tail_id: Option<u32>,
+    pub publish_time: Option<NaiveDateTime>,
+    pub underlying_bid: Price,
+    pub underlying_ask: Option<Price>,
+    pub bid: Price,
+    pub ask: Option<Price>,
+}
+
+/// DW28 live matrix, with the rows of each date from the latest working date.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LiveMatrix {
+    pub is_compressed: bool,
+    pub daily_rows: BTreeMap<NaiveDate, Vec<LiveMatrixRow>>,
+    pub ric_data: Option<RicData>,
+    /// Holidays published with the live matrix, to cross-check [crate::calendar::TradingCalendar].
+    pub holidays: Vec<NaiveDate>,
+}
+
+/// Terms and indicators of the DW published with the live matrix.
+/// 
+/// The values are None if not published, e.g. "N/A".
+#[derive(Debug, Clone, PartialEq)]
+pub struct RicData {
+    pub side: DWSide,
+    pub exercise_price: Option<Price>,
+    pub conversion_ratio: Option<f64>,
+    /// Annual dividend yield, e.g. 0.0146924 for 1.46924%.
+    pub dividend_yield: Option<f64>,
+    /// Annual implied volatility, e.g. 0.326 for 32.6%.
+    pub implied_volatility: Option<f64>,
+    /// Delta of the underlying option, e.g. 0.417 for 41.7%.
+    pub delta: Option<f64>,
+    /// Sensitivity as published, in the issuer's own units. Not the same as
+    /// [crate::analytics::greeks::Greeks::sensitivity].
+    pub sensitivity: Option<f64>,
+    /// Percentage change of the DW per 1% change of the underlying.
+    pub effective_gearing: Option<f64>,
+    pub underlying_price: Option<Price>,
+    /// Underlying price in THB, at [RicData::fx_rate].
+    pub underlying_price_adj: Option<Price>,
+    /// RIC of the underlying price, e.g. "S50cv1". See [crate::underlying::Underlying::ric].
+    pub underlying_ric: Option<Box<str>>,
+    /// Underlying ticker as published, e.g. "SET50*", "S&P500".
+    pub underlying_ticker: Option<Box<str>>,
+    pub last_trading_date: Option<NaiveDate>,
+    pub maturity: Option<NaiveDate>,
+}
+
+impl RicData {
+    /// Returns the ric_data parsed from given content of the live matrix.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `content` - Live matrix content from thaidw.com
+    fn parse ( content: &str ) -> Option<RicData> {
+        let data = serde_json::from_str::<HashMap<String, serde_json::Value>> (
+            RE_RIC_DATA.captures ( content )?.get ( 1 )?.as_str ( )
+        ).ok ( )?;
+        let text = |key: &str| match data.get ( key )? {
+            serde_json::Value::String ( s ) => Some ( s.replace ( ',', "" ) ),
+            serde_json::Value::Number ( n ) => Some ( n.to_string ( ) ),
+            _ => None,
+        };
+        let number = |key: &str| text ( key )?.parse::<f64> ( ).ok ( );
+        let price = |key: &str| text ( key )?.parse::<Price> ( ).ok ( );
+        let date = |key: &str| NaiveDate::parse_from_str ( text ( key )?.as_str ( ), "%d %b %y" ).ok ( );
+
+        Some ( RicData {
+            side: match text ( "type" ).as_deref ( ) {
+                Some ( "CALL" ) => DWSide::C,
+                Some ( "PUT" ) => DWSide::P,
+                _ => DWSide::Unknown,
+            },
+            exercise_price: price ( "exercise_price" ),
+            conversion_ratio: number ( "conv_ratio" ),
+            dividend_yield: number ( "div_yield" ),
+            implied_volatility: number ( "implied_volatility" ).map ( |v| v / 100.0 ),
+            delta: number ( "delta" ).map ( |v| v / 100.0 ),
+            sensitivity: number ( "sensitivity" ),
+            effective_gearing: number ( "effective_gearing" ),
+            underlying_price: price ( "underlying_price" ),
+            underlying_price_adj: price ( "underlying_price_adj" ),
+            underlying_ric: text ( "underlying_ric" ).map ( String::into_boxed_str ),
+            underlying_ticker: text ( "underlying_ticker" ).map ( String::into_boxed_str ),
+            last_trading_date: date ( "last_trading_date" ),
+            maturity: date ( "maturity" ),
+        } )
+    }
+
+    /// Returns the THB per one unit of the underlying currency used by the issuer, as the ratio
+    /// of [RicData::underlying_price_adj] to [RicData::underlying_price]. 1.0 for THB underlyings.
+    pub fn fx_rate ( &self ) -> Option<f64> {
+        let price = self.underlying_price?.to_f64 ( );
+        let price_adj = self.underlying_price_adj?.to_f64 ( );
+        if price > 0.0 && price_adj > 0.0 {
+            Some ( price_adj / price )
+        } else {
+            None
         }
+    }
 
+    /// Returns the contract terms of the DW, with the expiry at the last trading date.
+    /// 
+    /// For a foreign underlying, the conversion ratio is per THB of the underlying at
+    /// [RicData::fx_rate], so the DW prices in THB are valued from the underlying prices in its
+    /// own currency.
+    /// 
+    /// Returns None if any of the terms is not published.
+    pub fn to_dw_terms ( &self ) -> Option<DWTerms> {
+        if self.side == DWSide::Unknown {
+            return None;
+        }
+        Some ( DWTerms {
+            side: self.side.clone ( ),
+            strike: self.exercise_price?.to_f64 ( ),
+            conversion_ratio: self.conversion_ratio? / self.fx_rate ( ).unwrap_or ( 1.0 ),
+            expiry: self.last_trading_date?,
+        } )
+    }
+
+    /// Returns the expiry of given DW, with the published last trading date and maturity.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `dw_info` - DW info of the live matrix.
+    pub fn to_expiry ( &self, dw_info: &DWInfo ) -> Option<DWExpiry> {
+        Some ( dw_info.expiry ( )?.with_terms ( self.last_trading_date, self.maturity ) )
+    }
+
+    /// Returns the market parameters with the published dividend yield, and given interest rate.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `rate` - Annual interest rate.
+    pub fn to_market_params ( &self, rate: f64 ) -> MarketParams {
+        MarketParams {
+            rate,
+            dividend_yield: self.dividend_yield.unwrap_or ( 0.0 ),
+        }
+    }
+}
+
+impl LiveMatrix {
+    /// Returns the structured price table of the live matrix.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `dw_symbol` - DW symbol.
+    pub fn to_price_table ( &self, dw_symbol: &str ) -> PriceTable {
+        let mut table = PriceTable::new (
+            dw_symbol.into ( ),
+            self.daily_rows.values ( )
+                .flatten ( )
+                .filter_map ( |row| row.publish_time )
+                .max ( ),
+            self.daily_rows.iter ( )
+                .map ( |(date, rows)| PriceColumn::new (
+                    *date,
+                    rows.iter ( )
+                        .map ( |row| Quote {
+                            underlying_bid: row.underlying_bid,
+                            underlying_ask: row.underlying_ask,
+                            bid: row.bid,
+                            ask: row.ask,
+                        } )
+                        .collect ( )
+                ) )
+                .collect ( )
+        );
+        if let Ok ( dw_info ) = DWInfo::from_str ( dw_symbol ) {
+            table.underlying_currency = dw_info.underlying ( ).currency;
+        }
+        table.fx_rate = self.ric_data.as_ref ( ).and_then ( RicData::fx_rate );
+        table
+    }
+
+    /// Returns the map of underlying price to DW prices, with one DW price per date, sorted by date.
+    /// 
+    /// In the compressed format, each DW bid is keyed by its lowest underlying bid of the date, and
+    /// other dates are filled by the DW bid of the nearest lower underlying bid in that date.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `dw_info` - DW info of the live matrix, for the price digits of the keys, see
+    ///   [crate::underlying::Underlying::price_digits].
+    pub fn to_underlying_dw_price_table ( &self, dw_info: &DWInfo ) -> HashMap<i32, Vec<f32>> {
+        let price_digits = dw_info.underlying ( ).price_digits as usize;
+        // one ladder of (underlying, DW) pairs per date, sorted by underlying
+        let daily_ladders: Vec<Vec<(i32, f32)>> = self.daily_rows.values ( )
+            .map ( |rows| {
+                let mut ladder: Vec<(i32, f32)> = if self.is_compressed {
+                    // DW bid -> lowest underlying bid
+                    let mut dw_underlying_map = HashMap::<Price, Price>::new ( );
+                    rows.iter ( )
+                        .for_each ( |row| {
+                            let last_underlying_price = dw_underlying_map.get ( &row.bid );
+                            if last_underlying_price.is_none ( ) || *last_underlying_price.unwrap ( ) > row.underlying_bid {
+                                dw_underlying_map.insert ( row.bid, row.underlying_bid );
+                            }
+                        } );
+                    rows.iter ( )
+                        .filter ( |row| dw_underlying_map.get ( &row.bid ) == Some ( &row.underlying_bid ) )
+                        .map ( |row| ( row.underlying_bid.to_int_price ( price_digits ), row.bid.to_f32 ( ) ) )
+                        .collect ( )
+                } else {
+                    rows.iter ( )
+                        .map ( |row| ( row.underlying_bid.to_int_price ( price_digits ), row.bid.to_f32 ( ) ) )
+                        .collect ( )
+                };
+                ladder.sort_by_key ( |&(u, _)| u );
+                ladder.dedup_by_key ( |&mut (u, _)| u );
+                ladder
+            } )
+            .collect ( );
+
+        daily_ladders.iter ( )
+            .flatten ( )
+            .map ( |&(u, _)| u )
+            .collect::<BTreeSet<i32>> ( )
+            .into_iter ( )
+            .map ( |u| (
+                u,
+                daily_ladders.iter ( )
+                    .map ( |ladder| get_dw_price_from_ladder ( ladder, u ) )
+                    .collect ( )
+            ) )
+            .collect ( )
+    }
+}
+
+#[derive(Deserialize, Debug)]
+struct DWList {
+    #[serde(default)]
+    last_update: Option<String>,
+    symbols: Vec<DWListEntry>,
+}
+
+#[derive(Deserialize, Debug)]
+struct DWListEntry {
+    #[serde(default)]
+    ric: Option<String>,
+    security_code: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct CompressedRow {
+    #[serde(deserialize_with = "deserialize_str_or_number")]
+    head_tail_id: String,
+    publish_time: String,
+    #[serde(deserialize_with = "deserialize_str_or_number")]
+    underlying_bid: String,
+    #[serde(default, deserialize_with = "deserialize_option_str_or_number")]
+    underlying_ask: Option<String>,
+    #[serde(deserialize_with = "deserialize_str_or_number")]
+    bid: String,
+    #[serde(default, deserialize_with = "deserialize_option_str_or_number")]
+    ask: Option<String>,
+}
+
+impl CompressedRow {
+    /// Returns the row with parsed values, or None if any required value is invalid.
+    fn to_live_matrix_row ( &self ) -> Option<LiveMatrixRow> {
+        Some ( LiveMatrixRow {
+            head_tail_id: self.head_tail_id.parse ( ).ok ( ),
+            publish_time: NaiveDateTime::parse_from_str ( self.publish_time.as_str ( ), "%Y-%m-%d %H:%M:%S%.f" ).ok ( ),
+            underlying_bid: self.underlying_bid.parse ( ).ok ( )?,
+            underlying_ask: self.underlying_ask.as_ref ( ).and_then ( |u| u.parse ( ).ok ( ) ),
+            bid: self.bid.parse ( ).ok ( )?,
+            ask: self.ask.as_ref ( ).and_then ( |a| a.parse ( ).ok ( ) ),
+        } )
+    }
+}
+
+/// Deserialize value from json string or number into the string.
+fn deserialize_str_or_number<'de, D>(de: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match serde_json::Value::deserialize(de)? {
+        serde_json::Value::String(s) => Ok(s),
+        serde_json::Value::Number(n) => Ok(n.to_string()),
+        v => Err(serde::de::Error::custom(format!("Expected string or number: {}", v))),
+    }
+}
+
+/// Deserialize value from json string or number into Some<String>. If the original data is "null", then returns None
+fn deserialize_option_str_or_number<'de, D>(de: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match serde_json::Value::deserialize(de)? {
+        serde_json::Value::String(s) => Ok(Some(s)),
+        serde_json::Value::Number(n) => Ok(Some(n.to_string())),
+        _ => Ok(None),
+    }
+}
+
+impl DW28 {
+    /// Returns the catalogue of the listed DWs, with their RICs.
+    /// 
+    /// Returns [Error::DataNotFound] if the DW list cannot be fetched.
+    pub async fn get_catalogue ( ) -> Result<Catalogue, Error> {
+        let to_error = |e: &dyn std::fmt::Display| Error::DataNotFound {
+            symbol: "DW list".into ( ),
+            info: Some ( format ! ( "Failed to get {}: {}", DW_LIST_URL!(), e ) ),
+        };
+        let content =
+            Client::new ( )
+                .get (
+                    DW_LIST_URL!()
+                )
+                .send ( )
+                .await
+                .map_err ( |e| to_error ( &e ) )?
+                .text ( )
+                .await
+                .map_err ( |e| to_error ( &e ) )?
+            ;
+
+        DW28::parse_catalogue ( content.as_str ( ) )
+    }
+
+    /// Returns the catalogue parsed from given DW list content.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `content` - DW list content from thaidw.com
+    pub fn parse_catalogue ( content: &str ) -> Result<Catalogue, Error> {
+        let list = serde_json::from_str::<DWList> ( content )
+            .map_err ( |e| Error::FailedParsing { symbol: "DW list".into ( ), info: Some ( e.to_string ( ) ) } )?;
+        Ok ( Catalogue::new (
+            28,
+            list.last_update.as_deref ( ).and_then ( |time| NaiveDateTime::parse_from_str ( time, "%d %b %y %H:%M" ).ok ( ) ),
+            list.symbols.into_iter ( )
+                .map ( |entry| ( entry.security_code.into_boxed_str ( ), entry.ric.map ( String::into_boxed_str ) ) )
+        ) )
+    }
+
+    /// Returns the live matrix of given DW, with all rows of each date from the latest working date.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `dw_info` - DW info
+    pub async fn get_live_matrix ( dw_info: &DWInfo ) -> Result<LiveMatrix, Error> {
+        DW28::get_live_matrix_of ( &dw_info.symbol, Some ( DW28::get_predicted_dw_ric ( dw_info ) ) ).await
+    }
+
+    /// Returns the live matrix of given listed symbol, with all rows of each date from the latest
+    /// working date.
+    /// 
+    /// The RIC is looked up in the DW list, or given predicted RIC is used if not listed. The
+    /// holidays and dates published in the live matrix inconsistent with the SET calendar are
+    /// logged, see [crate::calendar::TradingCalendar::mismatches].
+    /// 
+    /// # Arguments
+    /// 
+    /// * `symbol` - Listed symbol
+    /// * `predicted_ric` - RIC to be used if the symbol is not in the DW list
+    pub async fn get_live_matrix_of ( symbol: &str, predicted_ric: Option<String> ) -> Result<LiveMatrix, Error> {
         let now = get_latest_working_date_time ( );
 
+        let to_error = |e: &dyn std::fmt::Display| Error::DataNotFound {
+            symbol: "DW list".into ( ),
+            info: Some ( format ! ( "Failed to get {}: {}", DW_LIST_URL!(), e ) ),
+        };
         let content =
             Client::new ( )
                 .get (
@@ -279,179 +678,222 @@ impl DWPriceTable for DW28 {
                 )
                 .send ( )
                 .await
-                .expect ( format! ( "Failed to connect to {}", DW_LIST_URL!() ).as_str ( ) )
+                .map_err ( |e| to_error ( &e ) )?
                 .text ( )
                 .await
-                .expect ( "Failed to get data from thaidw.com in text format" )
+                .map_err ( |e| to_error ( &e ) )?
             ;
             
         // debug ! ( "DW List: {}\n", content.as_str ( ) );
             
-        let mut dw_ric = String::new ( );
-        if let Some ( dw_ric_matches ) = DW_INFO_RE ! ( dw_info.symbol ).captures_iter ( content.as_str ( ) ).next ( ) {
-            if let Some ( dw_ric_capture ) = dw_ric_matches.get ( 1 ) {
-                if let Some ( dw_ric_matches ) = RE_DW_RIC.captures_iter ( dw_ric_capture.as_str ( ) ).next ( ) {
-                    if let Some ( found_dw_ric ) = dw_ric_matches.get ( 1 ) {
-                        dw_ric = found_dw_ric.as_str ( ).to_string ( );
-                    }
-                }
+        let dw_ric = match DW28::find_listed_ric ( content.as_str ( ), symbol ) {
+            Some ( dw_ric ) => dw_ric.to_string ( ),
+            None => {
+                let dw_ric = predicted_ric.ok_or_else ( || Error::DataNotFound { symbol: symbol.into ( ), info: Some ( "Not found in the DW list.".to_owned ( ) ) } )?;
+                debug ! ( "dw_ric is not found, so be predicted instead: {}", dw_ric );
+                dw_ric
             }
-        }
-
-        // debug ! ( "DW List dw_ric: {}", dw_ric );
-
-        if dw_ric.is_empty() {
-            dw_ric = DW28::get_predicted_dw_ric ( &dw_info );
-            debug ! ( "dw_ric is not found, so be predicted instead: {}", dw_ric );
-        }
+        };
 
+        let url = DW_PRICE_TABLE_URL ! ( dw_ric );
+        let to_error = |e: &dyn std::fmt::Display| Error::DataNotFound {
+            symbol: symbol.into ( ),
+            info: Some ( format ! ( "Failed to get {}: {}", url, e ) ),
+        };
         let content = Client::new ( )
             .get (
-                DW_PRICE_TABLE_URL ! ( dw_ric )
-                    .as_str ( )
+                url.as_str ( )
             )
             .send ( )
             .await
-            .expect ( "Failed to get ajax data from thaidw.com" )
+            .map_err ( |e| to_error ( &e ) )?
             .text ( )
             .await
-            .expect ( "Failed to get data from thaidw.com in text format" )
+            .map_err ( |e| to_error ( &e ) )?
             ;
             
-        let content = content.as_str ( );
-            
-        let mut dw_price_table = HashMap::<Self::UnderlyingType, Vec<f32>>::new ( );
-        
-        if RE_COMPRESSED_TYPE.is_match ( content ) {
-            let mut dw_underlying_map = HashMap::<Box<str>, f32>::new ( );
+        let today = NaiveDate::from_ymd_opt ( now.year ( ), now.month ( ), now.day ( ) )
+            .expect ( "Failed to get the date of the latest working date/time" );
 
-            let current_date = now.format ( "%Y-%m-%d" ).to_string ( );
-            let current_date = current_date.as_str ( );
+        let live_matrix = DW28::parse_live_matrix ( symbol, content.as_str ( ), today )?;
+        let dates = live_matrix.daily_rows.keys ( ).copied ( ).collect::<Vec<NaiveDate>> ( );
+        let mismatches = crate::calendar::TradingCalendar::set ( ).mismatches ( &live_matrix.holidays, &dates );
+        if ! mismatches.is_empty ( ) {
+            warn ! ( "Dates of {} inconsistent with the SET calendar: {:?}", symbol, mismatches );
+        }
+        Ok ( live_matrix )
+    }
+
+    /// Returns the RIC of given symbol in the DW list, if listed, case insensitive.
+    /// 
+    /// The entries are found by the security code key, so no pattern is compiled per symbol.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `content` - DW list content from thaidw.com
+    /// * `symbol` - Listed symbol
+    pub fn find_listed_ric<'a> ( content: &'a str, symbol: &str ) -> Option<&'a str> {
+        const SECURITY_CODE_KEY: &str = r#""security_code":""#;
+        let start = content.match_indices ( SECURITY_CODE_KEY )
+            .map ( |( i, _ )| i + SECURITY_CODE_KEY.len ( ) )
+            .find ( |&i| content.get ( i..i + symbol.len ( ) ).is_some_and ( |code| code.eq_ignore_ascii_case ( symbol ) )
+                && content [ i + symbol.len ( ).. ].starts_with ( '"' ) )?;
+        let entry_start = content [ ..start ].rfind ( '{' ).map_or ( 0, |i| i + 1 );
+        let entry_end = content [ start.. ].find ( '}' ).map_or ( content.len ( ), |i| start + i );
+        RE_DW_RIC.captures ( &content [ entry_start..entry_end ] )?
+            .get ( 1 )
+            .map ( |dw_ric| dw_ric.as_str ( ) )
+    }
 
+    /// Returns the live matrix parsed from given content, with the rows since given date.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `symbol` - Listed symbol
+    /// * `content` - Live matrix content from thaidw.com
+    /// * `today` - The first date of the live matrix to be returned
+    fn parse_live_matrix ( symbol: &str, content: &str, today: NaiveDate ) -> Result<LiveMatrix, Error> {
+        let mut daily_rows = BTreeMap::<NaiveDate, Vec<LiveMatrixRow>>::new ( );
+        
+        if RE_COMPRESSED_TYPE.is_match ( content ) {
             RE_DAILY_PRICE_LIST.captures_iter ( content )
-                .filter_map ( |daily_price_list_captures|
-                    if let Some ( date_match ) = daily_price_list_captures.get ( 1 ) {
-                        Some ( ( date_match.as_str ( ), daily_price_list_captures ) )
+                .filter_map ( |daily_price_list_captures| {
+                    let date_match = daily_price_list_captures.get ( 1 )?;
+                    let daily_data_match = daily_price_list_captures.get ( 2 )?;
+                    let date = NaiveDate::parse_from_str ( date_match.as_str ( ), "%Y-%m-%d" ).ok ( )?;
+                    if date >= today {
+                        Some ( ( date, daily_data_match ) )
                     } else {
                         None
                     }
-                )
-                .filter_map ( |(found_date, daily_price_list_captures)|
-                    if found_date == current_date {
-                        Some ( daily_price_list_captures )
-                    } else {
-                        None
-                    } )
-                .for_each ( |daily_price_list_captures| {
-                    if let Some ( daily_data_match ) = daily_price_list_captures.get ( 2 ) {
-                        RE_DW_DATA.captures_iter ( daily_data_match.as_str ( ) )
-                            .map ( |captures| captures.get ( 1 ) )
-                            .filter_map ( |daily_data_option_match| daily_data_option_match )
-                            .map ( |daily_data_match| daily_data_match.as_str ( ) )
-                            .filter_map ( |daily_data| {
-                                if let Some ( underlying_bid_captures ) = RE_UNDERLYING_BID_PRICE.captures_iter ( daily_data ).next ( ) {
-                                    if let Some ( underlying_bid ) = underlying_bid_captures.get ( 1 ) {
-                                        if let Ok ( underlying_bid_f32 ) = underlying_bid.as_str ( ).parse::<f32> ( ) {
-                                            Some ( ( underlying_bid_f32, daily_data ) )
-                                        } else {
-                                            None
-                                        }
-                                    } else {
-                                        None
-                                    }
-                                } else {
-                                    None
-                                }
-                            } )
-                            .for_each ( |(underlying_bid, daily_data)| {
-                                if let Some ( dw_bid_captures ) = RE_DW_BID_PRICE.captures_iter ( daily_data ).next ( ) {
-                                    if let Some ( dw_bid ) = dw_bid_captures.get ( 1 ) {
-                                        let dw_bid = dw_bid.as_str ( );
-                                        let last_underlying_price = dw_underlying_map.get ( dw_bid );
-                                        if last_underlying_price.is_none ( ) || *last_underlying_price.unwrap() > underlying_bid {
-                                            dw_underlying_map.insert ( dw_bid.to_string().into_boxed_str(), underlying_bid );
-                                        }
-                                    }
-                                }
-                            } )
-                    }
+                } )
+                .for_each ( |(date, daily_data_match)| {
+                    let rows = daily_rows.entry ( date ).or_default ( );
+
+                    RE_DW_DATA.captures_iter ( daily_data_match.as_str ( ) )
+                        .filter_map ( |captures| captures.get ( 1 ) )
+                        .filter_map ( |daily_data| serde_json::from_str::<CompressedRow> ( format ! ( "{{{}}}", daily_data.as_str ( ) ).as_str ( ) ).ok ( ) )
+                        .filter_map ( |compressed_row| compressed_row.to_live_matrix_row ( ) )
+                        .for_each ( |row| rows.push ( row ) );
                 } );
-                
-                dw_underlying_map.into_iter()
-                    .for_each ( |(dw, u)| {
-                        dw_price_table.insert (to_int_price ( u, DEFAULT_PRICE_DIGIT ), vec ! [dw.parse::<f32>().unwrap ( )]);
-                    } );
+
+            Ok ( LiveMatrix {
+                is_compressed: true,
+                daily_rows,
+                ric_data: RicData::parse ( content ),
+                holidays: parse_holidays ( content, today ),
+            } )
         } else {
             // noncompressed data
             
-            let mut date_index = HashMap::<NaiveDate, usize>::new ( );
+            // date key, e.g. "23 Dec" -> date of the column
+            let mut date_map = HashMap::<String, NaiveDate>::new ( );
             
             if let Some ( found_date_captures ) = RE_DATE_KEYS.captures_iter ( content ).next ( ) {
                 if let Some ( found_date_match ) = found_date_captures.get ( 1 ) {
-                    if let Ok ( dates ) = serde_json::from_str::<Vec<String>> ( found_date_match.as_str ( ) ) {
-                        
-                        let mut dates :Vec<NaiveDate> = dates.into_iter ( )
-                            //.map ( |s| format ! ( "{} {}", now.date().year(), s ) )
-                            .map ( |s| format ! ( "20 {}", s ) )
-                            .map ( |s| NaiveDate::parse_from_str ( &s, "%y %d %b").unwrap ( ) )
-                            .collect ( );
-
-                        dates.sort ( );
-                        
-                        dates.into_iter ( )
-                            .enumerate ( )
-                            .for_each ( |(idx, date)| {
-                                date_index.insert ( date, idx );
-                             } );
+                    if let Ok ( date_keys ) = serde_json::from_str::<Vec<String>> ( found_date_match.as_str ( ) ) {
+                        date_keys.into_iter ( )
+                            .filter_map ( |s| to_date_from_day_month ( &s, today ).map ( |date| ( s, date ) ) )
+                            .filter ( |(_, date)| *date >= today )
+                            .for_each ( |(date_key, date)| {
+                                date_map.insert ( date_key, date );
+                                daily_rows.insert ( date, Vec::new ( ) );
+                            } );
                     }
                 }
             }
+
+            if date_map.is_empty ( ) {
+                return Err ( Error::DataNotFound { symbol: symbol.into ( ), info: Some("Not found date in RE_DATE_KEYS.".to_owned()) } );
+            }
             
             if let Some ( found_date_captures ) = RE_NONCOMPRESSED_PRICE_TABLE.captures_iter ( content ).next ( ) {
-                let current_date = now.format ( "%d %b" ).to_string ( );
-                let current_date = current_date.as_str ( );
-                
                 if let Some ( price_column_match ) = found_date_captures.get ( 1 ) {
                     RE_PRICE_COLUMN.captures_iter ( price_column_match.as_str ( ) )
                         .filter_map ( |c| {
-                            if let Some ( column ) = c.get ( 2 ) {
-                                Some ( (
-                                    c.get ( 1 ).unwrap ( ).as_str ( ).parse::<f32> ( ).unwrap ( ),  // underlying price
-                                    column
-                                ) )
-                            } else {
-                                None
-                            }
+                            c.get ( 2 ).map ( |column| ( 
+                                c.get ( 1 ).unwrap ( ).as_str ( ).parse::<Price> ( ).unwrap ( ),  // underlying price
+                                column
+                            ) )
                         } )
                         .for_each ( |(underlying, price_column)| {
                             RE_DW_DATE_PRICE.captures_iter ( price_column.as_str ( ) )
                                 .filter_map ( |c| {
-                                    if let Some ( date ) = c.get ( 1 ) {
-                                        if date.as_str ( ) == current_date {
-                                            c.get ( 2 )
-                                        } else {
-                                            None
-                                        }
-                                    } else {
-                                        None
-                                    }
+                                    let date = date_map.get ( c.get ( 1 )?.as_str ( ) )?;
+                                    Some ( ( *date, c.get ( 2 )? ) )
                                  } )
-                                .for_each ( |d| {
-                                    dw_price_table.insert (
-                                        //to_int_price ( price_column.as_str ( ).parse ( ).unwrap ( ), DEFAULT_PRICE_DIGIT ),
-                                        to_int_price ( underlying, DEFAULT_PRICE_DIGIT ),
-                                        vec ! [ d.as_str ( ).parse::<f32> ( ).expect ( "Failed to parse from str to f32" ) ]
-                                    );
+                                .for_each ( |(date, d)| {
+                                    daily_rows.entry ( date )
+                                        .or_default ( )
+                                        .push ( LiveMatrixRow {
+                                            head_tail_id: None,
+                                            publish_time: None,
+                                            underlying_bid: underlying,
+                                            underlying_ask: None,
+                                            bid: d.as_str ( ).parse::<Price> ( ).expect ( "Failed to parse from str to Price" ),
+                                            ask: None,
+                                        } );
                                 } );
                         } );
 
                 } else {
-                    return Err ( Error::DataNotFound { symbol: dw_info.symbol.clone(), info: Some("Not found date in RE_NONCOMPRESSED_PRICE_TABLE.".to_owned()) } );
+                    return Err ( Error::DataNotFound { symbol: symbol.into ( ), info: Some("Not found date in RE_NONCOMPRESSED_PRICE_TABLE.".to_owned()) } );
                 }
             }
+
+            Ok ( LiveMatrix {
+                is_compressed: false,
+                daily_rows,
+                ric_data: RicData::parse ( content ),
+                holidays: parse_holidays ( content, today ),
+            } )
         }
+    }
+}
 
-        Ok ( dw_price_table )
+#[async_trait(?Send)]
+impl DWPriceTable for DW28 {
+    type UnderlyingType = i32;
+    type DWType = f32;
+
+    /// From given dw_info, fetch the live matrix, and returns the map of underlying price to DW prices.
+    /// 
+    /// Each list of DW prices has one price per date column, from the latest working date, sorted by date.
+    /// The price is 0.0 if the issuer does not quote at the underlying price on that date.
+    async fn get_underlying_dw_price_table ( dw_info: &DWInfo ) -> Result<HashMap<Self::UnderlyingType, Vec<Self::DWType>>, Error> {
+    //async fn get_underlying_dw_price_table ( dw_info: &DWInfo ) -> Result<HashMap<i32, Vec<f32>>, ()> {
+        #[cfg(test)]
+        {
+            let mut states = TEST_STATE
+                .lock()
+                .unwrap();
+
+            match states.get_mut(&thread::current().id()) {
+                None => {
+                    states
+                        .insert(
+                            thread::current().id(),
+                            TestState {
+                                count: 1,
+                                last_dw_symbol: dw_info.symbol.clone().to_string(),
+                            }
+                        );
+                },
+                Some(s) => {
+                    s.count += 1;
+                    s.last_dw_symbol = dw_info.symbol.clone().to_string();
+                }
+            }
+        }
+
+        let table = DW28::get_live_matrix ( dw_info ).await?.to_underlying_dw_price_table ( dw_info );
+        Ok ( QuoteConvention::for_dw ( dw_info ).normalize_underlying_dw_price_table ( dw_info, table ) )
+    }
+
+    /// From given dw_info, fetch the live matrix, and returns the price table with all rows of each date.
+    async fn get_price_table ( dw_info: &DWInfo ) -> Result<PriceTable, Error> {
+        let table = DW28::get_live_matrix ( dw_info ).await?.to_price_table ( &dw_info.symbol );
+        Ok ( QuoteConvention::for_dw ( dw_info ).normalize_price_table ( dw_info, table ) )
     }
 }
 
@@ -463,6 +905,7 @@ gen_mock!(dw28);
 pub mod dw28_tests {
     use super::*;
     use super::DW28;
+    use crate::underlying::Currency;
 
     use std::sync::Once;
     
@@ -474,6 +917,9 @@ pub mod dw28_tests {
                 let _ = env_logger::try_init ( );
             } );
         }
+        crate::clock::set_thread_clock_source ( Some ( std::sync::Arc::new ( crate::clock::FixedClock::at_bangkok (
+            chrono::NaiveDate::from_ymd_opt ( 2020, 12, 23 ).unwrap ( ).and_hms_opt ( 12, 0, 0 ).unwrap ( )
+        ) ) ) );
         HTML_MAP.with ( |html_map| {
             let mut result = html_map.borrow_mut ( );
             result.insert ( DW_LIST_URL!().to_string ( ).into_boxed_str(), target_list_html!().to_string ( ) );
@@ -510,35 +956,120 @@ pub mod dw28_tests {
         let table = out.unwrap ( );
         
         // check details
-        assert_eq ! ( table.keys ( ).len ( ), 25 );
-        
-        assert ! ( table.contains_key ( &90680 ) && table.get ( &90680 ) == Some ( & vec ! [ 0.69 ] ) );
-        assert ! ( table.contains_key ( &90630 ) && table.get ( &90630 ) == Some ( & vec ! [ 0.68 ] ) );
-        assert ! ( table.contains_key ( &90590 ) && table.get ( &90590 ) == Some ( & vec ! [ 0.67 ] ) );
-        assert ! ( table.contains_key ( &90550 ) && table.get ( &90550 ) == Some ( & vec ! [ 0.66 ] ) );
-        assert ! ( table.contains_key ( &90500 ) && table.get ( &90500 ) == Some ( & vec ! [ 0.65 ] ) );
-        assert ! ( table.contains_key ( &90460 ) && table.get ( &90460 ) == Some ( & vec ! [ 0.64 ] ) );
-        assert ! ( table.contains_key ( &90410 ) && table.get ( &90410 ) == Some ( & vec ! [ 0.63 ] ) );
-        assert ! ( table.contains_key ( &90370 ) && table.get ( &90370 ) == Some ( & vec ! [ 0.62 ] ) );
-        assert ! ( table.contains_key ( &90320 ) && table.get ( &90320 ) == Some ( & vec ! [ 0.61 ] ) );
-        assert ! ( table.contains_key ( &90280 ) && table.get ( &90280 ) == Some ( & vec ! [ 0.60 ] ) );
-        assert ! ( table.contains_key ( &90230 ) && table.get ( &90230 ) == Some ( & vec ! [ 0.59 ] ) );
-        assert ! ( table.contains_key ( &90180 ) && table.get ( &90180 ) == Some ( & vec ! [ 0.58 ] ) );
-        assert ! ( table.contains_key ( &90140 ) && table.get ( &90140 ) == Some ( & vec ! [ 0.57 ] ) );
-        assert ! ( table.contains_key ( &90090 ) && table.get ( &90090 ) == Some ( & vec ! [ 0.56 ] ) );
-        assert ! ( table.contains_key ( &90040 ) && table.get ( &90040 ) == Some ( & vec ! [ 0.55 ] ) );
-        assert ! ( table.contains_key ( &89990 ) && table.get ( &89990 ) == Some ( & vec ! [ 0.54 ] ) );
-        assert ! ( table.contains_key ( &89940 ) && table.get ( &89940 ) == Some ( & vec ! [ 0.53 ] ) );
-        assert ! ( table.contains_key ( &89890 ) && table.get ( &89890 ) == Some ( & vec ! [ 0.52 ] ) );
-        assert ! ( table.contains_key ( &89840 ) && table.get ( &89840 ) == Some ( & vec ! [ 0.51 ] ) );
-        assert ! ( table.contains_key ( &89790 ) && table.get ( &89790 ) == Some ( & vec ! [ 0.50 ] ) );
-        assert ! ( table.contains_key ( &89730 ) && table.get ( &89730 ) == Some ( & vec ! [ 0.49 ] ) );
-        assert ! ( table.contains_key ( &89680 ) && table.get ( &89680 ) == Some ( & vec ! [ 0.48 ] ) );
-        assert ! ( table.contains_key ( &89630 ) && table.get ( &89630 ) == Some ( & vec ! [ 0.47 ] ) );
-        assert ! ( table.contains_key ( &89570 ) && table.get ( &89570 ) == Some ( & vec ! [ 0.46 ] ) );
-        assert ! ( table.contains_key ( &89520 ) && table.get ( &89520 ) == Some ( & vec ! [ 0.45 ] ) );
+        assert_eq ! ( table.keys ( ).len ( ), 112 );
+        assert ! ( table.values ( ).all ( |dw_list| dw_list.len ( ) == 5 ) );
+        assert_eq ! ( table.get ( &90460 ), Some ( & vec ! [ 0.64, 0.57, 0.50, 0.19, 0.0 ] ) );
+        assert_eq ! ( table.get ( &89420 ), Some ( & vec ! [ 0.0, 0.0, 0.0, 0.06, 0.0 ] ) );
+        assert_eq ! ( table.get ( &91810 ), Some ( & vec ! [ 0.0, 0.0, 0.0, 0.0, 0.40 ] ) );
+
+        
+        assert ! ( table.contains_key ( &90680 ) && table.get ( &90680 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.69 ) );
+        assert ! ( table.contains_key ( &90630 ) && table.get ( &90630 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.68 ) );
+        assert ! ( table.contains_key ( &90590 ) && table.get ( &90590 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.67 ) );
+        assert ! ( table.contains_key ( &90550 ) && table.get ( &90550 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.66 ) );
+        assert ! ( table.contains_key ( &90500 ) && table.get ( &90500 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.65 ) );
+        assert ! ( table.contains_key ( &90460 ) && table.get ( &90460 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.64 ) );
+        assert ! ( table.contains_key ( &90410 ) && table.get ( &90410 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.63 ) );
+        assert ! ( table.contains_key ( &90370 ) && table.get ( &90370 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.62 ) );
+        assert ! ( table.contains_key ( &90320 ) && table.get ( &90320 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.61 ) );
+        assert ! ( table.contains_key ( &90280 ) && table.get ( &90280 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.60 ) );
+        assert ! ( table.contains_key ( &90230 ) && table.get ( &90230 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.59 ) );
+        assert ! ( table.contains_key ( &90180 ) && table.get ( &90180 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.58 ) );
+        assert ! ( table.contains_key ( &90140 ) && table.get ( &90140 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.57 ) );
+        assert ! ( table.contains_key ( &90090 ) && table.get ( &90090 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.56 ) );
+        assert ! ( table.contains_key ( &90040 ) && table.get ( &90040 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.55 ) );
+        assert ! ( table.contains_key ( &89990 ) && table.get ( &89990 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.54 ) );
+        assert ! ( table.contains_key ( &89940 ) && table.get ( &89940 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.53 ) );
+        assert ! ( table.contains_key ( &89890 ) && table.get ( &89890 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.52 ) );
+        assert ! ( table.contains_key ( &89840 ) && table.get ( &89840 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.51 ) );
+        assert ! ( table.contains_key ( &89790 ) && table.get ( &89790 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.50 ) );
+        assert ! ( table.contains_key ( &89730 ) && table.get ( &89730 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.49 ) );
+        assert ! ( table.contains_key ( &89680 ) && table.get ( &89680 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.48 ) );
+        assert ! ( table.contains_key ( &89630 ) && table.get ( &89630 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.47 ) );
+        assert ! ( table.contains_key ( &89570 ) && table.get ( &89570 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.46 ) );
+        assert ! ( table.contains_key ( &89520 ) && table.get ( &89520 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.45 ) );
     }
     
+    #[tokio::test]
+    pub async fn test_get_price_table_compressed_s50_call ( ) {
+        setup ( );
+        HTML_MAP.with ( |html_map| {
+            let mut result = html_map.borrow_mut ( );
+            result.insert ( target_html_compressed_s50_call_url!().into_boxed_str ( ), target_html_compressed_s50_call!().to_string ( ) );
+        } );
+        
+        let out = DW28::get_price_table(& DWInfo::from_str ( "S5028C2012D" ).unwrap ( ) )
+            .await;
+        
+        assert ! ( out.is_ok ( ) );
+        
+        let table = out.unwrap ( );
+
+        assert_eq ! ( &*table.dw_symbol, "S5028C2012D" );
+        assert_eq ! ( table.publish_time, NaiveDate::from_ymd_opt ( 2020, 12, 23 ).unwrap ( ).and_hms_opt ( 12, 26, 22 ) );
+        assert_eq ! ( table.dates ( ), vec ! [
+            NaiveDate::from_ymd_opt ( 2020, 12, 23 ).unwrap ( ),
+            NaiveDate::from_ymd_opt ( 2020, 12, 24 ).unwrap ( ),
+            NaiveDate::from_ymd_opt ( 2020, 12, 25 ).unwrap ( ),
+            NaiveDate::from_ymd_opt ( 2020, 12, 28 ).unwrap ( ),
+            NaiveDate::from_ymd_opt ( 2020, 12, 29 ).unwrap ( ),
+        ] );
+
+        // full ladder, both head and tail rows of each DW tick
+        let column = &table.columns [ 0 ];
+        assert_eq ! ( column.quotes.len ( ), 50 );
+        assert_eq ! ( column.quotes.first ( ), Some ( & Quote {
+            underlying_bid: Price::new ( 89520, 2 ),
+            underlying_ask: Some ( Price::new ( 89530, 2 ) ),
+            bid: Price::new ( 45, 2 ),
+            ask: Some ( Price::new ( 54, 2 ) ),
+        } ) );
+        assert_eq ! ( column.quotes.last ( ), Some ( & Quote {
+            underlying_bid: Price::new ( 90710, 2 ),
+            underlying_ask: Some ( Price::new ( 90720, 2 ) ),
+            bid: Price::new ( 69, 2 ),
+            ask: Some ( Price::new ( 79, 2 ) ),
+        } ) );
+
+        // same as the lowest underlying of the DW bid in the map of underlying price to DW prices
+        let date = NaiveDate::from_ymd_opt ( 2020, 12, 23 ).unwrap ( );
+        assert_eq ! ( table.find_underlying ( date, Price::new ( 55, 2 ) ), Some ( Price::new ( 90040, 2 ) ) );
+        assert_eq ! ( table.get_quote ( date, Price::new ( 90045, 2 ), Match::Floor ).map ( |q| q.bid ), Some ( Price::new ( 55, 2 ) ) );
+    }
+
+    #[tokio::test]
+    pub async fn test_get_live_matrix_compressed_s50_call ( ) {
+        setup ( );
+        HTML_MAP.with ( |html_map| {
+            let mut result = html_map.borrow_mut ( );
+            result.insert ( target_html_compressed_s50_call_url!().into_boxed_str ( ), target_html_compressed_s50_call!().to_string ( ) );
+        } );
+        
+        let out = DW28::get_live_matrix(& DWInfo::from_str ( "S5028C2012D" ).unwrap ( ) )
+            .await;
+        
+        assert ! ( out.is_ok ( ) );
+        
+        let live_matrix = out.unwrap ( );
+
+        assert ! ( live_matrix.is_compressed );
+        assert_eq ! ( live_matrix.daily_rows.len ( ), 5 );
+
+        let rows = live_matrix.daily_rows.get ( &NaiveDate::from_ymd_opt ( 2020, 12, 29 ).unwrap ( ) ).unwrap ( );
+        assert_eq ! ( rows.len ( ), 80 );
+        assert_eq ! ( rows [ 0 ], LiveMatrixRow {
+            head_tail_id: Some ( 1 ),
+            publish_time: NaiveDate::from_ymd_opt ( 2020, 12, 23 ).unwrap ( ).and_hms_opt ( 12, 26, 22 ),
+            underlying_bid: "918.20".parse ( ).unwrap ( ),
+            underlying_ask: Some ( "918.30".parse ( ).unwrap ( ) ),
+            bid: "0.40".parse ( ).unwrap ( ),
+            ask: Some ( "0.50".parse ( ).unwrap ( ) ),
+        } );
+        assert_eq ! ( rows [ 0 ].underlying_bid.to_string ( ), "918.20" );
+        assert_eq ! ( rows [ 1 ].head_tail_id, Some ( 1 ) );
+        assert_eq ! ( rows [ 2 ].head_tail_id, Some ( 2 ) );
+    }
+
     #[tokio::test]
     pub async fn test_get_underlying_dw_price_table_compressed_hsi_call ( ) {
         setup ( );
@@ -558,37 +1089,40 @@ pub mod dw28_tests {
         let table = out.unwrap ( );
         
         // check details
-        assert_eq ! ( table.keys ( ).len ( ), 29 );
-        
-        assert ! ( table.contains_key ( &2678100 ) && table.get ( &2678100 ) == Some ( & vec ! [ 0.32 ] ) );
-        assert ! ( table.contains_key ( &2676200 ) && table.get ( &2676200 ) == Some ( & vec ! [ 0.31 ] ) );
-        assert ! ( table.contains_key ( &2674100 ) && table.get ( &2674100 ) == Some ( & vec ! [ 0.30 ] ) );
-        assert ! ( table.contains_key ( &2672000 ) && table.get ( &2672000 ) == Some ( & vec ! [ 0.29 ] ) );
-        assert ! ( table.contains_key ( &2669900 ) && table.get ( &2669900 ) == Some ( & vec ! [ 0.28 ] ) );
-        assert ! ( table.contains_key ( &2667700 ) && table.get ( &2667700 ) == Some ( & vec ! [ 0.27 ] ) );
-        assert ! ( table.contains_key ( &2665500 ) && table.get ( &2665500 ) == Some ( & vec ! [ 0.26 ] ) );
-        assert ! ( table.contains_key ( &2663200 ) && table.get ( &2663200 ) == Some ( & vec ! [ 0.25 ] ) );
-        assert ! ( table.contains_key ( &2660800 ) && table.get ( &2660800 ) == Some ( & vec ! [ 0.24 ] ) );
-        assert ! ( table.contains_key ( &2658400 ) && table.get ( &2658400 ) == Some ( & vec ! [ 0.23 ] ) );
-        assert ! ( table.contains_key ( &2655800 ) && table.get ( &2655800 ) == Some ( & vec ! [ 0.22 ] ) );
-        assert ! ( table.contains_key ( &2653200 ) && table.get ( &2653200 ) == Some ( & vec ! [ 0.21 ] ) );
-        assert ! ( table.contains_key ( &2650500 ) && table.get ( &2650500 ) == Some ( & vec ! [ 0.20 ] ) );
-        assert ! ( table.contains_key ( &2647700 ) && table.get ( &2647700 ) == Some ( & vec ! [ 0.19 ] ) );
-        assert ! ( table.contains_key ( &2644800 ) && table.get ( &2644800 ) == Some ( & vec ! [ 0.18 ] ) );
-        assert ! ( table.contains_key ( &2641800 ) && table.get ( &2641800 ) == Some ( & vec ! [ 0.17 ] ) );
-        assert ! ( table.contains_key ( &2638600 ) && table.get ( &2638600 ) == Some ( & vec ! [ 0.16 ] ) );
-        assert ! ( table.contains_key ( &2635200 ) && table.get ( &2635200 ) == Some ( & vec ! [ 0.15 ] ) );
-        assert ! ( table.contains_key ( &2631700 ) && table.get ( &2631700 ) == Some ( & vec ! [ 0.14 ] ) );
-        assert ! ( table.contains_key ( &2628000 ) && table.get ( &2628000 ) == Some ( & vec ! [ 0.13 ] ) );
-        assert ! ( table.contains_key ( &2624000 ) && table.get ( &2624000 ) == Some ( & vec ! [ 0.12 ] ) );
-        assert ! ( table.contains_key ( &2619800 ) && table.get ( &2619800 ) == Some ( & vec ! [ 0.11 ] ) );
-        assert ! ( table.contains_key ( &2615200 ) && table.get ( &2615200 ) == Some ( & vec ! [ 0.10 ] ) );
-        assert ! ( table.contains_key ( &2610300 ) && table.get ( &2610300 ) == Some ( & vec ! [ 0.09 ] ) );
-        assert ! ( table.contains_key ( &2604900 ) && table.get ( &2604900 ) == Some ( & vec ! [ 0.08 ] ) );
-        assert ! ( table.contains_key ( &2598800 ) && table.get ( &2598800 ) == Some ( & vec ! [ 0.07 ] ) );
-        assert ! ( table.contains_key ( &2591900 ) && table.get ( &2591900 ) == Some ( & vec ! [ 0.06 ] ) );
-        assert ! ( table.contains_key ( &2584000 ) && table.get ( &2584000 ) == Some ( & vec ! [ 0.05 ] ) );
-        assert ! ( table.contains_key ( &2574400 ) && table.get ( &2574400 ) == Some ( & vec ! [ 0.04 ] ) );
+        assert_eq ! ( table.keys ( ).len ( ), 171 );
+        assert ! ( table.values ( ).all ( |dw_list| dw_list.len ( ) == 5 ) );
+        assert_eq ! ( table.get ( &26751 ), Some ( & vec ! [ 0.30, 0.27, 0.24, 0.11, 0.05 ] ) );
+
+        
+        assert ! ( table.contains_key ( &26781 ) && table.get ( &26781 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.32 ) );
+        assert ! ( table.contains_key ( &26762 ) && table.get ( &26762 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.31 ) );
+        assert ! ( table.contains_key ( &26741 ) && table.get ( &26741 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.30 ) );
+        assert ! ( table.contains_key ( &26720 ) && table.get ( &26720 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.29 ) );
+        assert ! ( table.contains_key ( &26699 ) && table.get ( &26699 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.28 ) );
+        assert ! ( table.contains_key ( &26677 ) && table.get ( &26677 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.27 ) );
+        assert ! ( table.contains_key ( &26655 ) && table.get ( &26655 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.26 ) );
+        assert ! ( table.contains_key ( &26632 ) && table.get ( &26632 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.25 ) );
+        assert ! ( table.contains_key ( &26608 ) && table.get ( &26608 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.24 ) );
+        assert ! ( table.contains_key ( &26584 ) && table.get ( &26584 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.23 ) );
+        assert ! ( table.contains_key ( &26558 ) && table.get ( &26558 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.22 ) );
+        assert ! ( table.contains_key ( &26532 ) && table.get ( &26532 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.21 ) );
+        assert ! ( table.contains_key ( &26505 ) && table.get ( &26505 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.20 ) );
+        assert ! ( table.contains_key ( &26477 ) && table.get ( &26477 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.19 ) );
+        assert ! ( table.contains_key ( &26448 ) && table.get ( &26448 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.18 ) );
+        assert ! ( table.contains_key ( &26418 ) && table.get ( &26418 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.17 ) );
+        assert ! ( table.contains_key ( &26386 ) && table.get ( &26386 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.16 ) );
+        assert ! ( table.contains_key ( &26352 ) && table.get ( &26352 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.15 ) );
+        assert ! ( table.contains_key ( &26317 ) && table.get ( &26317 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.14 ) );
+        assert ! ( table.contains_key ( &26280 ) && table.get ( &26280 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.13 ) );
+        assert ! ( table.contains_key ( &26240 ) && table.get ( &26240 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.12 ) );
+        assert ! ( table.contains_key ( &26198 ) && table.get ( &26198 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.11 ) );
+        assert ! ( table.contains_key ( &26152 ) && table.get ( &26152 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.10 ) );
+        assert ! ( table.contains_key ( &26103 ) && table.get ( &26103 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.09 ) );
+        assert ! ( table.contains_key ( &26049 ) && table.get ( &26049 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.08 ) );
+        assert ! ( table.contains_key ( &25988 ) && table.get ( &25988 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.07 ) );
+        assert ! ( table.contains_key ( &25919 ) && table.get ( &25919 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.06 ) );
+        assert ! ( table.contains_key ( &25840 ) && table.get ( &25840 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.05 ) );
+        assert ! ( table.contains_key ( &25744 ) && table.get ( &25744 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.04 ) );
     }
     
     #[tokio::test]
@@ -610,49 +1144,51 @@ pub mod dw28_tests {
         let table = out.unwrap ( );
         
         // check details
-        assert_eq ! ( table.keys ( ).len ( ), 41 );
+        assert_eq ! ( table.keys ( ).len ( ), 202 );
+        assert ! ( table.values ( ).all ( |dw_list| dw_list.len ( ) == 5 ) );
+        assert_eq ! ( table.get ( &25389 ), Some ( & vec ! [ 0.27, 0.26, 0.25, 0.22, 0.21 ] ) );
         
-        assert ! ( table.contains_key ( &2739400 ) && table.get ( &2739400 ) == Some ( & vec ! [ 0.05 ] ) );
-        assert ! ( table.contains_key ( &2720300 ) && table.get ( &2720300 ) == Some ( & vec ! [ 0.06 ] ) );
-        assert ! ( table.contains_key ( &2703600 ) && table.get ( &2703600 ) == Some ( & vec ! [ 0.07 ] ) );
-        assert ! ( table.contains_key ( &2688800 ) && table.get ( &2688800 ) == Some ( & vec ! [ 0.08 ] ) );
-        assert ! ( table.contains_key ( &2675500 ) && table.get ( &2675500 ) == Some ( & vec ! [ 0.09 ] ) );
-        assert ! ( table.contains_key ( &2663400 ) && table.get ( &2663400 ) == Some ( & vec ! [ 0.10 ] ) );
-        assert ! ( table.contains_key ( &2652300 ) && table.get ( &2652300 ) == Some ( & vec ! [ 0.11 ] ) );
-        assert ! ( table.contains_key ( &2642000 ) && table.get ( &2642000 ) == Some ( & vec ! [ 0.12 ] ) );
-        assert ! ( table.contains_key ( &2632400 ) && table.get ( &2632400 ) == Some ( & vec ! [ 0.13 ] ) );
-        assert ! ( table.contains_key ( &2623400 ) && table.get ( &2623400 ) == Some ( & vec ! [ 0.14 ] ) );
-        assert ! ( table.contains_key ( &2615000 ) && table.get ( &2615000 ) == Some ( & vec ! [ 0.15 ] ) );
-        assert ! ( table.contains_key ( &2606900 ) && table.get ( &2606900 ) == Some ( & vec ! [ 0.16 ] ) );
-        assert ! ( table.contains_key ( &2599400 ) && table.get ( &2599400 ) == Some ( & vec ! [ 0.17 ] ) );
-        assert ! ( table.contains_key ( &2592100 ) && table.get ( &2592100 ) == Some ( & vec ! [ 0.18 ] ) );
-        assert ! ( table.contains_key ( &2585200 ) && table.get ( &2585200 ) == Some ( & vec ! [ 0.19 ] ) );
-        assert ! ( table.contains_key ( &2578600 ) && table.get ( &2578600 ) == Some ( & vec ! [ 0.20 ] ) );
-        assert ! ( table.contains_key ( &2572300 ) && table.get ( &2572300 ) == Some ( & vec ! [ 0.21 ] ) );
-        assert ! ( table.contains_key ( &2566200 ) && table.get ( &2566200 ) == Some ( & vec ! [ 0.22 ] ) );
-        assert ! ( table.contains_key ( &2560400 ) && table.get ( &2560400 ) == Some ( & vec ! [ 0.23 ] ) );
-        assert ! ( table.contains_key ( &2554700 ) && table.get ( &2554700 ) == Some ( & vec ! [ 0.24 ] ) );
-        assert ! ( table.contains_key ( &2549300 ) && table.get ( &2549300 ) == Some ( & vec ! [ 0.25 ] ) );
-        assert ! ( table.contains_key ( &2544000 ) && table.get ( &2544000 ) == Some ( & vec ! [ 0.26 ] ) );
-        assert ! ( table.contains_key ( &2538900 ) && table.get ( &2538900 ) == Some ( & vec ! [ 0.27 ] ) );
-        assert ! ( table.contains_key ( &2533900 ) && table.get ( &2533900 ) == Some ( & vec ! [ 0.28 ] ) );
-        assert ! ( table.contains_key ( &2529100 ) && table.get ( &2529100 ) == Some ( & vec ! [ 0.29 ] ) );
-        assert ! ( table.contains_key ( &2524400 ) && table.get ( &2524400 ) == Some ( & vec ! [ 0.30 ] ) );
-        assert ! ( table.contains_key ( &2519900 ) && table.get ( &2519900 ) == Some ( & vec ! [ 0.31 ] ) );
-        assert ! ( table.contains_key ( &2515500 ) && table.get ( &2515500 ) == Some ( & vec ! [ 0.32 ] ) );
-        assert ! ( table.contains_key ( &2511100 ) && table.get ( &2511100 ) == Some ( & vec ! [ 0.33 ] ) );
-        assert ! ( table.contains_key ( &2506900 ) && table.get ( &2506900 ) == Some ( & vec ! [ 0.34 ] ) );
-        assert ! ( table.contains_key ( &2502800 ) && table.get ( &2502800 ) == Some ( & vec ! [ 0.35 ] ) );
-        assert ! ( table.contains_key ( &2498800 ) && table.get ( &2498800 ) == Some ( & vec ! [ 0.36 ] ) );
-        assert ! ( table.contains_key ( &2494900 ) && table.get ( &2494900 ) == Some ( & vec ! [ 0.37 ] ) );
-        assert ! ( table.contains_key ( &2491000 ) && table.get ( &2491000 ) == Some ( & vec ! [ 0.38 ] ) );
-        assert ! ( table.contains_key ( &2487300 ) && table.get ( &2487300 ) == Some ( & vec ! [ 0.39 ] ) );
-        assert ! ( table.contains_key ( &2483600 ) && table.get ( &2483600 ) == Some ( & vec ! [ 0.40 ] ) );
-        assert ! ( table.contains_key ( &2480000 ) && table.get ( &2480000 ) == Some ( & vec ! [ 0.41 ] ) );
-        assert ! ( table.contains_key ( &2476400 ) && table.get ( &2476400 ) == Some ( & vec ! [ 0.42 ] ) );
-        assert ! ( table.contains_key ( &2473000 ) && table.get ( &2473000 ) == Some ( & vec ! [ 0.43 ] ) );
-        assert ! ( table.contains_key ( &2469500 ) && table.get ( &2469500 ) == Some ( & vec ! [ 0.44 ] ) );
-        assert ! ( table.contains_key ( &2466200 ) && table.get ( &2466200 ) == Some ( & vec ! [ 0.45 ] ) );
+        assert ! ( table.contains_key ( &27394 ) && table.get ( &27394 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.05 ) );
+        assert ! ( table.contains_key ( &27203 ) && table.get ( &27203 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.06 ) );
+        assert ! ( table.contains_key ( &27036 ) && table.get ( &27036 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.07 ) );
+        assert ! ( table.contains_key ( &26888 ) && table.get ( &26888 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.08 ) );
+        assert ! ( table.contains_key ( &26755 ) && table.get ( &26755 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.09 ) );
+        assert ! ( table.contains_key ( &26634 ) && table.get ( &26634 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.10 ) );
+        assert ! ( table.contains_key ( &26523 ) && table.get ( &26523 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.11 ) );
+        assert ! ( table.contains_key ( &26420 ) && table.get ( &26420 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.12 ) );
+        assert ! ( table.contains_key ( &26324 ) && table.get ( &26324 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.13 ) );
+        assert ! ( table.contains_key ( &26234 ) && table.get ( &26234 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.14 ) );
+        assert ! ( table.contains_key ( &26150 ) && table.get ( &26150 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.15 ) );
+        assert ! ( table.contains_key ( &26069 ) && table.get ( &26069 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.16 ) );
+        assert ! ( table.contains_key ( &25994 ) && table.get ( &25994 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.17 ) );
+        assert ! ( table.contains_key ( &25921 ) && table.get ( &25921 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.18 ) );
+        assert ! ( table.contains_key ( &25852 ) && table.get ( &25852 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.19 ) );
+        assert ! ( table.contains_key ( &25786 ) && table.get ( &25786 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.20 ) );
+        assert ! ( table.contains_key ( &25723 ) && table.get ( &25723 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.21 ) );
+        assert ! ( table.contains_key ( &25662 ) && table.get ( &25662 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.22 ) );
+        assert ! ( table.contains_key ( &25604 ) && table.get ( &25604 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.23 ) );
+        assert ! ( table.contains_key ( &25547 ) && table.get ( &25547 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.24 ) );
+        assert ! ( table.contains_key ( &25493 ) && table.get ( &25493 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.25 ) );
+        assert ! ( table.contains_key ( &25440 ) && table.get ( &25440 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.26 ) );
+        assert ! ( table.contains_key ( &25389 ) && table.get ( &25389 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.27 ) );
+        assert ! ( table.contains_key ( &25339 ) && table.get ( &25339 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.28 ) );
+        assert ! ( table.contains_key ( &25291 ) && table.get ( &25291 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.29 ) );
+        assert ! ( table.contains_key ( &25244 ) && table.get ( &25244 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.30 ) );
+        assert ! ( table.contains_key ( &25199 ) && table.get ( &25199 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.31 ) );
+        assert ! ( table.contains_key ( &25155 ) && table.get ( &25155 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.32 ) );
+        assert ! ( table.contains_key ( &25111 ) && table.get ( &25111 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.33 ) );
+        assert ! ( table.contains_key ( &25069 ) && table.get ( &25069 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.34 ) );
+        assert ! ( table.contains_key ( &25028 ) && table.get ( &25028 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.35 ) );
+        assert ! ( table.contains_key ( &24988 ) && table.get ( &24988 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.36 ) );
+        assert ! ( table.contains_key ( &24949 ) && table.get ( &24949 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.37 ) );
+        assert ! ( table.contains_key ( &24910 ) && table.get ( &24910 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.38 ) );
+        assert ! ( table.contains_key ( &24873 ) && table.get ( &24873 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.39 ) );
+        assert ! ( table.contains_key ( &24836 ) && table.get ( &24836 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.40 ) );
+        assert ! ( table.contains_key ( &24800 ) && table.get ( &24800 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.41 ) );
+        assert ! ( table.contains_key ( &24764 ) && table.get ( &24764 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.42 ) );
+        assert ! ( table.contains_key ( &24730 ) && table.get ( &24730 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.43 ) );
+        assert ! ( table.contains_key ( &24695 ) && table.get ( &24695 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.44 ) );
+        assert ! ( table.contains_key ( &24662 ) && table.get ( &24662 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.45 ) );
     }
     
     #[tokio::test]
@@ -674,49 +1210,51 @@ pub mod dw28_tests {
         let table = out.unwrap ( );
         
         // check details
-        assert_eq ! ( table.keys ( ).len ( ), 41 );
+        assert_eq ! ( table.keys ( ).len ( ), 89 );
+        assert ! ( table.values ( ).all ( |dw_list| dw_list.len ( ) == 5 ) );
+        assert_eq ! ( table.get ( &368700 ), Some ( & vec ! [ 0.79, 0.79, 0.78, 0.75, 0.74 ] ) );
         
-        assert ! ( table.contains_key ( &375900 ) && table.get ( &375900 ) == Some ( & vec ! [ 0.65 ] ) );
-        assert ! ( table.contains_key ( &375300 ) && table.get ( &375300 ) == Some ( & vec ! [ 0.66 ] ) );
-        assert ! ( table.contains_key ( &374800 ) && table.get ( &374800 ) == Some ( & vec ! [ 0.67 ] ) );
-        assert ! ( table.contains_key ( &374300 ) && table.get ( &374300 ) == Some ( & vec ! [ 0.68 ] ) );
-        assert ! ( table.contains_key ( &373700 ) && table.get ( &373700 ) == Some ( & vec ! [ 0.69 ] ) );
-        assert ! ( table.contains_key ( &373200 ) && table.get ( &373200 ) == Some ( & vec ! [ 0.70 ] ) );
-        assert ! ( table.contains_key ( &372700 ) && table.get ( &372700 ) == Some ( & vec ! [ 0.71 ] ) );
-        assert ! ( table.contains_key ( &372200 ) && table.get ( &372200 ) == Some ( & vec ! [ 0.72 ] ) );
-        assert ! ( table.contains_key ( &371700 ) && table.get ( &371700 ) == Some ( & vec ! [ 0.73 ] ) );
-        assert ! ( table.contains_key ( &371200 ) && table.get ( &371200 ) == Some ( & vec ! [ 0.74 ] ) );
-        assert ! ( table.contains_key ( &370700 ) && table.get ( &370700 ) == Some ( & vec ! [ 0.75 ] ) );
-        assert ! ( table.contains_key ( &370200 ) && table.get ( &370200 ) == Some ( & vec ! [ 0.76 ] ) );
-        assert ! ( table.contains_key ( &369700 ) && table.get ( &369700 ) == Some ( & vec ! [ 0.77 ] ) );
-        assert ! ( table.contains_key ( &369200 ) && table.get ( &369200 ) == Some ( & vec ! [ 0.78 ] ) );
-        assert ! ( table.contains_key ( &368700 ) && table.get ( &368700 ) == Some ( & vec ! [ 0.79 ] ) );
-        assert ! ( table.contains_key ( &368300 ) && table.get ( &368300 ) == Some ( & vec ! [ 0.80 ] ) );
-        assert ! ( table.contains_key ( &367800 ) && table.get ( &367800 ) == Some ( & vec ! [ 0.81 ] ) );
-        assert ! ( table.contains_key ( &367400 ) && table.get ( &367400 ) == Some ( & vec ! [ 0.82 ] ) );
-        assert ! ( table.contains_key ( &366900 ) && table.get ( &366900 ) == Some ( & vec ! [ 0.83 ] ) );
-        assert ! ( table.contains_key ( &366400 ) && table.get ( &366400 ) == Some ( & vec ! [ 0.84 ] ) );
-        assert ! ( table.contains_key ( &366000 ) && table.get ( &366000 ) == Some ( & vec ! [ 0.85 ] ) );
-        assert ! ( table.contains_key ( &365600 ) && table.get ( &365600 ) == Some ( & vec ! [ 0.86 ] ) );
-        assert ! ( table.contains_key ( &365100 ) && table.get ( &365100 ) == Some ( & vec ! [ 0.87 ] ) );
-        assert ! ( table.contains_key ( &364700 ) && table.get ( &364700 ) == Some ( & vec ! [ 0.88 ] ) );
-        assert ! ( table.contains_key ( &364300 ) && table.get ( &364300 ) == Some ( & vec ! [ 0.89 ] ) );
-        assert ! ( table.contains_key ( &363800 ) && table.get ( &363800 ) == Some ( & vec ! [ 0.90 ] ) );
-        assert ! ( table.contains_key ( &363400 ) && table.get ( &363400 ) == Some ( & vec ! [ 0.91 ] ) );
-        assert ! ( table.contains_key ( &363000 ) && table.get ( &363000 ) == Some ( & vec ! [ 0.92 ] ) );
-        assert ! ( table.contains_key ( &362600 ) && table.get ( &362600 ) == Some ( & vec ! [ 0.93 ] ) );
-        assert ! ( table.contains_key ( &362200 ) && table.get ( &362200 ) == Some ( & vec ! [ 0.94 ] ) );
-        assert ! ( table.contains_key ( &361800 ) && table.get ( &361800 ) == Some ( & vec ! [ 0.95 ] ) );
-        assert ! ( table.contains_key ( &361400 ) && table.get ( &361400 ) == Some ( & vec ! [ 0.96 ] ) );
-        assert ! ( table.contains_key ( &361000 ) && table.get ( &361000 ) == Some ( & vec ! [ 0.97 ] ) );
-        assert ! ( table.contains_key ( &360600 ) && table.get ( &360600 ) == Some ( & vec ! [ 0.98 ] ) );
-        assert ! ( table.contains_key ( &360200 ) && table.get ( &360200 ) == Some ( & vec ! [ 0.99 ] ) );
-        assert ! ( table.contains_key ( &359800 ) && table.get ( &359800 ) == Some ( & vec ! [ 1.00 ] ) );
-        assert ! ( table.contains_key ( &359400 ) && table.get ( &359400 ) == Some ( & vec ! [ 1.01 ] ) );
-        assert ! ( table.contains_key ( &359000 ) && table.get ( &359000 ) == Some ( & vec ! [ 1.02 ] ) );
-        assert ! ( table.contains_key ( &358600 ) && table.get ( &358600 ) == Some ( & vec ! [ 1.03 ] ) );
-        assert ! ( table.contains_key ( &358300 ) && table.get ( &358300 ) == Some ( & vec ! [ 1.04 ] ) );
-        assert ! ( table.contains_key ( &357900 ) && table.get ( &357900 ) == Some ( & vec ! [ 1.05 ] ) );
+        assert ! ( table.contains_key ( &375900 ) && table.get ( &375900 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.65 ) );
+        assert ! ( table.contains_key ( &375300 ) && table.get ( &375300 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.66 ) );
+        assert ! ( table.contains_key ( &374800 ) && table.get ( &374800 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.67 ) );
+        assert ! ( table.contains_key ( &374300 ) && table.get ( &374300 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.68 ) );
+        assert ! ( table.contains_key ( &373700 ) && table.get ( &373700 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.69 ) );
+        assert ! ( table.contains_key ( &373200 ) && table.get ( &373200 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.70 ) );
+        assert ! ( table.contains_key ( &372700 ) && table.get ( &372700 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.71 ) );
+        assert ! ( table.contains_key ( &372200 ) && table.get ( &372200 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.72 ) );
+        assert ! ( table.contains_key ( &371700 ) && table.get ( &371700 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.73 ) );
+        assert ! ( table.contains_key ( &371200 ) && table.get ( &371200 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.74 ) );
+        assert ! ( table.contains_key ( &370700 ) && table.get ( &370700 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.75 ) );
+        assert ! ( table.contains_key ( &370200 ) && table.get ( &370200 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.76 ) );
+        assert ! ( table.contains_key ( &369700 ) && table.get ( &369700 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.77 ) );
+        assert ! ( table.contains_key ( &369200 ) && table.get ( &369200 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.78 ) );
+        assert ! ( table.contains_key ( &368700 ) && table.get ( &368700 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.79 ) );
+        assert ! ( table.contains_key ( &368300 ) && table.get ( &368300 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.80 ) );
+        assert ! ( table.contains_key ( &367800 ) && table.get ( &367800 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.81 ) );
+        assert ! ( table.contains_key ( &367400 ) && table.get ( &367400 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.82 ) );
+        assert ! ( table.contains_key ( &366900 ) && table.get ( &366900 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.83 ) );
+        assert ! ( table.contains_key ( &366400 ) && table.get ( &366400 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.84 ) );
+        assert ! ( table.contains_key ( &366000 ) && table.get ( &366000 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.85 ) );
+        assert ! ( table.contains_key ( &365600 ) && table.get ( &365600 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.86 ) );
+        assert ! ( table.contains_key ( &365100 ) && table.get ( &365100 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.87 ) );
+        assert ! ( table.contains_key ( &364700 ) && table.get ( &364700 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.88 ) );
+        assert ! ( table.contains_key ( &364300 ) && table.get ( &364300 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.89 ) );
+        assert ! ( table.contains_key ( &363800 ) && table.get ( &363800 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.90 ) );
+        assert ! ( table.contains_key ( &363400 ) && table.get ( &363400 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.91 ) );
+        assert ! ( table.contains_key ( &363000 ) && table.get ( &363000 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.92 ) );
+        assert ! ( table.contains_key ( &362600 ) && table.get ( &362600 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.93 ) );
+        assert ! ( table.contains_key ( &362200 ) && table.get ( &362200 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.94 ) );
+        assert ! ( table.contains_key ( &361800 ) && table.get ( &361800 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.95 ) );
+        assert ! ( table.contains_key ( &361400 ) && table.get ( &361400 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.96 ) );
+        assert ! ( table.contains_key ( &361000 ) && table.get ( &361000 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.97 ) );
+        assert ! ( table.contains_key ( &360600 ) && table.get ( &360600 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.98 ) );
+        assert ! ( table.contains_key ( &360200 ) && table.get ( &360200 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.99 ) );
+        assert ! ( table.contains_key ( &359800 ) && table.get ( &359800 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 1.00 ) );
+        assert ! ( table.contains_key ( &359400 ) && table.get ( &359400 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 1.01 ) );
+        assert ! ( table.contains_key ( &359000 ) && table.get ( &359000 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 1.02 ) );
+        assert ! ( table.contains_key ( &358600 ) && table.get ( &358600 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 1.03 ) );
+        assert ! ( table.contains_key ( &358300 ) && table.get ( &358300 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 1.04 ) );
+        assert ! ( table.contains_key ( &357900 ) && table.get ( &357900 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 1.05 ) );
     }
     
     #[tokio::test]
@@ -739,50 +1277,89 @@ pub mod dw28_tests {
         
         // check details
         assert_eq ! ( table.keys ( ).len ( ), 41 );
+        assert ! ( table.values ( ).all ( |dw_list| dw_list.len ( ) == 5 ) );
+
+        // 23 Dec, 24 Dec, 25 Dec, 28 Dec, 29 Dec
+        assert_eq ! ( table.get ( &16850 ), Some ( & vec ! [ 0.03, 0.03, 0.03, 0.03, 0.02 ] ) );
+        assert_eq ! ( table.get ( &18800 ), Some ( & vec ! [ 0.10, 0.09, 0.09, 0.08, 0.08 ] ) );
         
-        assert ! ( table.contains_key ( &16850 ) && table.get ( &16850 ) == Some ( & vec ! [ 0.03 ] ) );
-        assert ! ( table.contains_key ( &16900 ) && table.get ( &16900 ) == Some ( & vec ! [ 0.03 ] ) );
-        assert ! ( table.contains_key ( &16950 ) && table.get ( &16950 ) == Some ( & vec ! [ 0.04 ] ) );
-        assert ! ( table.contains_key ( &17000 ) && table.get ( &17000 ) == Some ( & vec ! [ 0.04 ] ) );
-        assert ! ( table.contains_key ( &17050 ) && table.get ( &17050 ) == Some ( & vec ! [ 0.04 ] ) );
-        assert ! ( table.contains_key ( &17100 ) && table.get ( &17100 ) == Some ( & vec ! [ 0.04 ] ) );
-        assert ! ( table.contains_key ( &17150 ) && table.get ( &17150 ) == Some ( & vec ! [ 0.04 ] ) );
-        assert ! ( table.contains_key ( &17200 ) && table.get ( &17200 ) == Some ( & vec ! [ 0.04 ] ) );
-        assert ! ( table.contains_key ( &17250 ) && table.get ( &17250 ) == Some ( & vec ! [ 0.04 ] ) );
-        assert ! ( table.contains_key ( &17300 ) && table.get ( &17300 ) == Some ( & vec ! [ 0.04 ] ) );
-        assert ! ( table.contains_key ( &17350 ) && table.get ( &17350 ) == Some ( & vec ! [ 0.04 ] ) );
-        assert ! ( table.contains_key ( &17400 ) && table.get ( &17400 ) == Some ( & vec ! [ 0.05 ] ) );
-        assert ! ( table.contains_key ( &17450 ) && table.get ( &17450 ) == Some ( & vec ! [ 0.05 ] ) );
-        assert ! ( table.contains_key ( &17500 ) && table.get ( &17500 ) == Some ( & vec ! [ 0.05 ] ) );
-        assert ! ( table.contains_key ( &17550 ) && table.get ( &17550 ) == Some ( & vec ! [ 0.05 ] ) );
-        assert ! ( table.contains_key ( &17600 ) && table.get ( &17600 ) == Some ( & vec ! [ 0.05 ] ) );
-        assert ! ( table.contains_key ( &17650 ) && table.get ( &17650 ) == Some ( & vec ! [ 0.05 ] ) );
-        assert ! ( table.contains_key ( &17700 ) && table.get ( &17700 ) == Some ( & vec ! [ 0.05 ] ) );
-        assert ! ( table.contains_key ( &17750 ) && table.get ( &17750 ) == Some ( & vec ! [ 0.06 ] ) );
-        assert ! ( table.contains_key ( &17800 ) && table.get ( &17800 ) == Some ( & vec ! [ 0.06 ] ) );
-        assert ! ( table.contains_key ( &17850 ) && table.get ( &17850 ) == Some ( & vec ! [ 0.06 ] ) );
-        assert ! ( table.contains_key ( &17900 ) && table.get ( &17900 ) == Some ( & vec ! [ 0.06 ] ) );
-        assert ! ( table.contains_key ( &17950 ) && table.get ( &17950 ) == Some ( & vec ! [ 0.06 ] ) );
-        assert ! ( table.contains_key ( &18000 ) && table.get ( &18000 ) == Some ( & vec ! [ 0.06 ] ) );
-        assert ! ( table.contains_key ( &18050 ) && table.get ( &18050 ) == Some ( & vec ! [ 0.07 ] ) );
-        assert ! ( table.contains_key ( &18100 ) && table.get ( &18100 ) == Some ( & vec ! [ 0.07 ] ) );
-        assert ! ( table.contains_key ( &18150 ) && table.get ( &18150 ) == Some ( & vec ! [ 0.07 ] ) );
-        assert ! ( table.contains_key ( &18200 ) && table.get ( &18200 ) == Some ( & vec ! [ 0.07 ] ) );
-        assert ! ( table.contains_key ( &18250 ) && table.get ( &18250 ) == Some ( & vec ! [ 0.07 ] ) );
-        assert ! ( table.contains_key ( &18300 ) && table.get ( &18300 ) == Some ( & vec ! [ 0.07 ] ) );
-        assert ! ( table.contains_key ( &18350 ) && table.get ( &18350 ) == Some ( & vec ! [ 0.08 ] ) );
-        assert ! ( table.contains_key ( &18400 ) && table.get ( &18400 ) == Some ( & vec ! [ 0.08 ] ) );
-        assert ! ( table.contains_key ( &18450 ) && table.get ( &18450 ) == Some ( & vec ! [ 0.08 ] ) );
-        assert ! ( table.contains_key ( &18500 ) && table.get ( &18500 ) == Some ( & vec ! [ 0.08 ] ) );
-        assert ! ( table.contains_key ( &18550 ) && table.get ( &18550 ) == Some ( & vec ! [ 0.08 ] ) );
-        assert ! ( table.contains_key ( &18600 ) && table.get ( &18600 ) == Some ( & vec ! [ 0.09 ] ) );
-        assert ! ( table.contains_key ( &18650 ) && table.get ( &18650 ) == Some ( & vec ! [ 0.09 ] ) );
-        assert ! ( table.contains_key ( &18700 ) && table.get ( &18700 ) == Some ( & vec ! [ 0.09 ] ) );
-        assert ! ( table.contains_key ( &18750 ) && table.get ( &18750 ) == Some ( & vec ! [ 0.09 ] ) );
-        assert ! ( table.contains_key ( &18800 ) && table.get ( &18800 ) == Some ( & vec ! [ 0.10 ] ) );
+        let out = DW28::get_price_table(& DWInfo::from_str ( "ADVA28C2102L" ).unwrap ( ) )
+            .await;
+        assert ! ( out.is_ok ( ) );
+        let price_table = out.unwrap ( );
+        assert_eq ! ( price_table.publish_time, None );
+        assert_eq ! ( price_table.columns.len ( ), 5 );
+        assert ! ( price_table.columns.iter ( ).all ( |c| c.quotes.len ( ) == 41 ) );
+        assert_eq ! ( price_table.columns [ 4 ].quotes [ 1 ], Quote {
+            underlying_bid: Price::new ( 16850, 2 ),
+            underlying_ask: None,
+            bid: Price::new ( 2, 2 ),
+            ask: None,
+        } );
+        
+        assert ! ( table.contains_key ( &16850 ) && table.get ( &16850 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.03 ) );
+        assert ! ( table.contains_key ( &16900 ) && table.get ( &16900 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.03 ) );
+        assert ! ( table.contains_key ( &16950 ) && table.get ( &16950 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.04 ) );
+        assert ! ( table.contains_key ( &17000 ) && table.get ( &17000 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.04 ) );
+        assert ! ( table.contains_key ( &17050 ) && table.get ( &17050 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.04 ) );
+        assert ! ( table.contains_key ( &17100 ) && table.get ( &17100 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.04 ) );
+        assert ! ( table.contains_key ( &17150 ) && table.get ( &17150 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.04 ) );
+        assert ! ( table.contains_key ( &17200 ) && table.get ( &17200 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.04 ) );
+        assert ! ( table.contains_key ( &17250 ) && table.get ( &17250 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.04 ) );
+        assert ! ( table.contains_key ( &17300 ) && table.get ( &17300 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.04 ) );
+        assert ! ( table.contains_key ( &17350 ) && table.get ( &17350 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.04 ) );
+        assert ! ( table.contains_key ( &17400 ) && table.get ( &17400 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.05 ) );
+        assert ! ( table.contains_key ( &17450 ) && table.get ( &17450 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.05 ) );
+        assert ! ( table.contains_key ( &17500 ) && table.get ( &17500 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.05 ) );
+        assert ! ( table.contains_key ( &17550 ) && table.get ( &17550 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.05 ) );
+        assert ! ( table.contains_key ( &17600 ) && table.get ( &17600 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.05 ) );
+        assert ! ( table.contains_key ( &17650 ) && table.get ( &17650 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.05 ) );
+        assert ! ( table.contains_key ( &17700 ) && table.get ( &17700 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.05 ) );
+        assert ! ( table.contains_key ( &17750 ) && table.get ( &17750 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.06 ) );
+        assert ! ( table.contains_key ( &17800 ) && table.get ( &17800 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.06 ) );
+        assert ! ( table.contains_key ( &17850 ) && table.get ( &17850 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.06 ) );
+        assert ! ( table.contains_key ( &17900 ) && table.get ( &17900 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.06 ) );
+        assert ! ( table.contains_key ( &17950 ) && table.get ( &17950 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.06 ) );
+        assert ! ( table.contains_key ( &18000 ) && table.get ( &18000 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.06 ) );
+        assert ! ( table.contains_key ( &18050 ) && table.get ( &18050 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.07 ) );
+        assert ! ( table.contains_key ( &18100 ) && table.get ( &18100 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.07 ) );
+        assert ! ( table.contains_key ( &18150 ) && table.get ( &18150 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.07 ) );
+        assert ! ( table.contains_key ( &18200 ) && table.get ( &18200 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.07 ) );
+        assert ! ( table.contains_key ( &18250 ) && table.get ( &18250 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.07 ) );
+        assert ! ( table.contains_key ( &18300 ) && table.get ( &18300 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.07 ) );
+        assert ! ( table.contains_key ( &18350 ) && table.get ( &18350 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.08 ) );
+        assert ! ( table.contains_key ( &18400 ) && table.get ( &18400 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.08 ) );
+        assert ! ( table.contains_key ( &18450 ) && table.get ( &18450 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.08 ) );
+        assert ! ( table.contains_key ( &18500 ) && table.get ( &18500 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.08 ) );
+        assert ! ( table.contains_key ( &18550 ) && table.get ( &18550 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.08 ) );
+        assert ! ( table.contains_key ( &18600 ) && table.get ( &18600 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.09 ) );
+        assert ! ( table.contains_key ( &18650 ) && table.get ( &18650 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.09 ) );
+        assert ! ( table.contains_key ( &18700 ) && table.get ( &18700 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.09 ) );
+        assert ! ( table.contains_key ( &18750 ) && table.get ( &18750 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.09 ) );
+        assert ! ( table.contains_key ( &18800 ) && table.get ( &18800 ).map ( |dw_list| dw_list [ 0 ] ) == Some ( 0.10 ) );
 
     }
     
+    #[test]
+    pub fn test_to_date_from_day_month ( ) {
+        let base = NaiveDate::from_ymd_opt ( 2020, 12, 23 ).unwrap ( );
+        assert_eq ! ( to_date_from_day_month ( "23 Dec", base ), NaiveDate::from_ymd_opt ( 2020, 12, 23 ) );
+        assert_eq ! ( to_date_from_day_month ( "04 Jan", base ), NaiveDate::from_ymd_opt ( 2021, 1, 4 ) );
+        assert_eq ! ( to_date_from_day_month ( "01 Dec", base ), NaiveDate::from_ymd_opt ( 2020, 12, 1 ) );
+        assert_eq ! ( to_date_from_day_month ( "XX Dec", base ), None );
+    }
+
+    #[test]
+    pub fn test_get_dw_price_from_ladder ( ) {
+        let ladder = vec ! [ ( 100, 0.01 ), ( 150, 0.02 ), ( 210, 0.03 ) ];
+        assert_eq ! ( get_dw_price_from_ladder ( &ladder, 99 ), 0.0 );
+        assert_eq ! ( get_dw_price_from_ladder ( &ladder, 100 ), 0.01 );
+        assert_eq ! ( get_dw_price_from_ladder ( &ladder, 149 ), 0.01 );
+        assert_eq ! ( get_dw_price_from_ladder ( &ladder, 150 ), 0.02 );
+        assert_eq ! ( get_dw_price_from_ladder ( &ladder, 210 ), 0.03 );
+        assert_eq ! ( get_dw_price_from_ladder ( &ladder, 211 ), 0.0 );
+    }
+    
     #[test]
     pub fn test_get_predicted_dw_ric ( ) {
         setup ( );
@@ -794,7 +1371,7 @@ pub mod dw28_tests {
             broker_id: 11,
             side: DWSide::C,
             expire_yymm: [ 0x32, 0x33, 0x34, 0x35 ],
-            series: 'A',
+            series: "A".into ( ),
         };
         let ric = DW28::get_predicted_dw_ric ( &dw_info );
         assert_eq! ( ric, "ABC11C345.BK" );
@@ -806,7 +1383,7 @@ pub mod dw28_tests {
             broker_id: 11,
             side: DWSide::C,
             expire_yymm: [ 0x32, 0x33, 0x34, 0x35 ],
-            series: 'A',
+            series: "A".into ( ),
         };
         let ric = DW28::get_predicted_dw_ric ( &dw_info );
         assert_eq! ( ric, "XYZ11C345.BK" );
@@ -818,7 +1395,7 @@ pub mod dw28_tests {
             broker_id: 99,
             side: DWSide::C,
             expire_yymm: [ 0x32, 0x33, 0x34, 0x35 ],
-            series: 'A',
+            series: "A".into ( ),
         };
         let ric = DW28::get_predicted_dw_ric ( &dw_info );
         assert_eq! ( ric, "ABC99C345.BK" );
@@ -830,7 +1407,7 @@ pub mod dw28_tests {
             broker_id: 11,
             side: DWSide::C,
             expire_yymm: [ 0x32, 0x33, 0x34, 0x35 ],
-            series: 'A',
+            series: "A".into ( ),
         };
         let ric = DW28::get_predicted_dw_ric ( &dw_info );
         assert_eq! ( ric, "ABC11C345.BK" );
@@ -842,7 +1419,7 @@ pub mod dw28_tests {
             broker_id: 11,
             side: DWSide::P,
             expire_yymm: [ 0x32, 0x33, 0x34, 0x35 ],
-            series: 'B',
+            series: "B".into ( ),
         };
         let ric = DW28::get_predicted_dw_ric ( &dw_info );
         assert_eq! ( ric, "ABC11P345.BK" );
@@ -854,7 +1431,7 @@ pub mod dw28_tests {
             broker_id: 11,
             side: DWSide::Unknown,
             expire_yymm: [ 0x32, 0x33, 0x34, 0x35 ],
-            series: 'A',
+            series: "A".into ( ),
         };
         let ric = DW28::get_predicted_dw_ric ( &dw_info );
         assert_eq! ( ric, "ABC11.345.BK" );
@@ -866,7 +1443,7 @@ pub mod dw28_tests {
             broker_id: 11,
             side: DWSide::C,
             expire_yymm: [ 0x50, 0x33, 0x34, 0x35 ],
-            series: 'B',
+            series: "B".into ( ),
         };
         let ric = DW28::get_predicted_dw_ric ( &dw_info );
         assert_eq! ( ric, "ABC11C345.BK" );
@@ -878,7 +1455,7 @@ pub mod dw28_tests {
             broker_id: 11,
             side: DWSide::C,
             expire_yymm: [ 0x32, 0x39, 0x34, 0x35 ],
-            series: 'B',
+            series: "B".into ( ),
         };
         let ric = DW28::get_predicted_dw_ric ( &dw_info );
         assert_eq! ( ric, "ABC11C945.BK" );
@@ -890,7 +1467,7 @@ pub mod dw28_tests {
             broker_id: 11,
             side: DWSide::C,
             expire_yymm: [ 0x32, 0x33, 0x30, 0x30 ],
-            series: 'B',
+            series: "B".into ( ),
         };
         let ric = DW28::get_predicted_dw_ric ( &dw_info );
         assert_eq! ( ric, "ABC11C300.BK" );
@@ -902,9 +1479,302 @@ pub mod dw28_tests {
             broker_id: 11,
             side: DWSide::C,
             expire_yymm: [ 0x32, 0x33, 0x34, 0x35 ],
-            series: 'B',
+            series: "B".into ( ),
         };
         let ric = DW28::get_predicted_dw_ric ( &dw_info );
         assert_eq! ( ric, "ABC11C345.BK" );
     }
+
+    #[tokio::test]
+    pub async fn test_implied_volatility_compared_with_ric_data ( ) {
+        setup ( );
+        HTML_MAP.with ( |html_map| {
+            let mut result = html_map.borrow_mut ( );
+            result.insert ( target_html_compressed_s50_call_url!().into_boxed_str ( ), target_html_compressed_s50_call!().to_string ( ) );
+            result.insert ( target_html_compressed_advanc_call_url!().into_boxed_str ( ), target_html_compressed_advanc_call!().to_string ( ) );
+        } );
+
+        let date = NaiveDate::from_ymd_opt ( 2020, 12, 23 ).unwrap ( );
+
+        for ( symbol, published ) in [ ( "S5028C2012D", 0.326 ), ( "ADVA28C2102L", 0.634 ) ] {
+            let dw_info = DWInfo::from_str ( symbol ).unwrap ( );
+            let live_matrix = DW28::get_live_matrix ( &dw_info ).await.unwrap ( );
+            let ric_data = live_matrix.ric_data.clone ( ).unwrap ( );
+            assert_eq ! ( ric_data.implied_volatility, Some ( published ) );
+
+            let terms = ric_data.to_dw_terms ( ).unwrap ( );
+            let params = ric_data.to_market_params ( 0.0 );
+
+            // median of the rows on the date
+            let mut volatilities = terms.implied_volatilities ( &live_matrix.to_price_table ( symbol ), &params )
+                .into_iter ( )
+                .filter ( |iv| iv.date == date )
+                .map ( |iv| iv.volatility )
+                .collect::<Vec<f64>> ( );
+            assert ! ( ! volatilities.is_empty ( ) );
+            volatilities.sort_by ( |a, b| a.partial_cmp ( b ).unwrap ( ) );
+            let volatility = volatilities [ volatilities.len ( ) / 2 ];
+
+            assert ! ( ( volatility - published ).abs ( ) < 0.03, "{}: computed={} published={}", symbol, volatility, published );
+        }
+    }
+
+    #[test]
+    fn test_foreign_underlying_with_fx_rate ( ) {
+        let date = NaiveDate::from_ymd_opt ( 2020, 12, 23 ).unwrap ( );
+
+        for ( symbol, content, currency, fx_rate, published ) in [
+            ( "HSI28C2012C", target_html_compressed_hsi_call!(), Currency::Hkd, 102298.37 / 26262.0, 22.4 ),
+            ( "HSI28P2101C", target_html_compressed_hsi_put!(), Currency::Hkd, 102298.37 / 26262.0, 31.8 ),
+            ( "SPX28P2103A", target_html_compressed_spx_put!(), Currency::Usd, 110939.70 / 3673.5, 33.2 ),
+        ] {
+            let live_matrix = DW28::parse_live_matrix ( symbol, content.as_str ( ), date ).unwrap ( );
+            let ric_data = live_matrix.ric_data.clone ( ).unwrap ( );
+            assert ! ( ( ric_data.fx_rate ( ).unwrap ( ) - fx_rate ).abs ( ) < 1e-9, "{}", symbol );
+
+            let table = live_matrix.to_price_table ( symbol );
+            assert_eq ! ( table.underlying_currency, currency );
+            assert_eq ! ( table.fx_rate, ric_data.fx_rate ( ) );
+            let underlying = table.columns [ 0 ].quotes [ 0 ].underlying_bid;
+            assert ! ( ( table.to_thb ( underlying ).unwrap ( ) - underlying.to_f64 ( ) * fx_rate ).abs ( ) < 1e-6 );
+
+            // the DW prices in THB, valued from the underlying prices in its own currency
+            let terms = ric_data.to_dw_terms ( ).unwrap ( );
+            let mut volatilities = terms.implied_volatilities ( &table, &ric_data.to_market_params ( 0.0 ) )
+                .into_iter ( )
+                .filter ( |iv| iv.date == date )
+                .map ( |iv| iv.volatility )
+                .collect::<Vec<f64>> ( );
+            assert ! ( ! volatilities.is_empty ( ) );
+            volatilities.sort_by ( |a, b| a.partial_cmp ( b ).unwrap ( ) );
+            let volatility = volatilities [ volatilities.len ( ) / 2 ];
+            assert ! ( ( volatility - published / 100.0 ).abs ( ) < 0.03, "{}: computed={} published={}%", symbol, volatility, published );
+        }
+
+        // THB underlyings
+        let ric_data = RicData::parse ( target_html_compressed_advanc_call!().as_str ( ) ).unwrap ( );
+        assert_eq ! ( ric_data.fx_rate ( ), Some ( 1.0 ) );
+        let table = DW28::parse_live_matrix ( "S5028C2012D", target_html_compressed_s50_call!().as_str ( ), date )
+            .unwrap ( )
+            .to_price_table ( "S5028C2012D" );
+        assert_eq ! ( table.underlying_currency, Currency::Thb );
+        assert_eq ! ( table.to_thb ( Price::new ( 90050, 2 ) ), Some ( 900.5 ) );
+    }
+
+    #[tokio::test]
+    pub async fn test_greeks_compared_with_ric_data ( ) {
+        setup ( );
+        HTML_MAP.with ( |html_map| {
+            let mut result = html_map.borrow_mut ( );
+            result.insert ( target_html_compressed_s50_call_url!().into_boxed_str ( ), target_html_compressed_s50_call!().to_string ( ) );
+            result.insert ( target_html_compressed_advanc_call_url!().into_boxed_str ( ), target_html_compressed_advanc_call!().to_string ( ) );
+        } );
+
+        let date = NaiveDate::from_ymd_opt ( 2020, 12, 23 ).unwrap ( );
+
+        // THB underlyings only, as the foreign ones need the FX rate
+        // the window spans a few DW ticks, as the tables are step functions
+        for ( symbol, window_ticks ) in [ ( "S5028C2012D", 100 ), ( "ADVA28C2102L", 12 ) ] {
+            let dw_info = DWInfo::from_str ( symbol ).unwrap ( );
+            let live_matrix = DW28::get_live_matrix ( &dw_info ).await.unwrap ( );
+            let ric_data = live_matrix.ric_data.clone ( ).unwrap ( );
+            let ladder = TickLadder::for_underlying ( &dw_info.underlying_symbol );
+
+            let greeks = get_table_greeks ( &live_matrix.to_price_table ( symbol ), date, ric_data.underlying_price.unwrap ( ), &ladder, window_ticks ).unwrap ( );
+
+            let delta = greeks.delta ( ric_data.conversion_ratio.unwrap ( ) );
+            let published = ric_data.delta.unwrap ( );
+            assert ! ( ( delta - published ).abs ( ) < 0.1 * published, "{}: delta computed={} published={}", symbol, delta, published );
+
+            // published on the mid price, computed on the bid
+            let effective_gearing = greeks.effective_gearing.unwrap ( );
+            let published = ric_data.effective_gearing.unwrap ( );
+            assert ! ( ( effective_gearing - published ).abs ( ) < 0.15 * published, "{}: effective gearing computed={} published={}", symbol, effective_gearing, published );
+
+            assert ! ( greeks.sensitivity.unwrap ( ) > 0.0 );
+        }
+    }
+
+    #[test]
+    fn test_parse_holidays ( ) {
+        let today = NaiveDate::from_ymd_opt ( 2020, 12, 23 ).unwrap ( );
+        assert_eq ! ( parse_holidays ( r#""holidays":[],"2020-12-23""#, today ), vec ! [ ] );
+        assert_eq ! ( parse_holidays ( r#""holidays":["2020-12-31","04 Jan"]"#, today ), vec ! [
+            NaiveDate::from_ymd_opt ( 2020, 12, 31 ).unwrap ( ),
+            NaiveDate::from_ymd_opt ( 2021, 1, 4 ).unwrap ( ),
+        ] );
+    }
+
+    #[tokio::test]
+    pub async fn test_calendar_compared_with_live_matrix ( ) {
+        setup ( );
+        HTML_MAP.with ( |html_map| {
+            let mut result = html_map.borrow_mut ( );
+            result.insert ( target_html_compressed_s50_call_url!().into_boxed_str ( ), target_html_compressed_s50_call!().to_string ( ) );
+        } );
+
+        let live_matrix = DW28::get_live_matrix ( &DWInfo::from_str ( "S5028C2012D" ).unwrap ( ) ).await.unwrap ( );
+        let dates = live_matrix.daily_rows.keys ( ).copied ( ).collect::<Vec<NaiveDate>> ( );
+        assert_eq ! ( dates.len ( ), 5 );
+        assert_eq ! ( crate::calendar::TradingCalendar::set ( ).mismatches ( &live_matrix.holidays, &dates ), vec ! [ ] );
+    }
+
+    #[tokio::test]
+    pub async fn test_live_matrix_holidays_not_in_calendar ( ) {
+        setup ( );
+        HTML_MAP.with ( |html_map| {
+            let mut result = html_map.borrow_mut ( );
+            result.insert ( target_html_compressed_s50_call_url!().into_boxed_str ( ), target_html_compressed_s50_call!().replace ( r#""holidays":[]"#, r#""holidays":["2030-01-07"]"# ) );
+        } );
+
+        let holiday = NaiveDate::from_ymd_opt ( 2030, 1, 7 ).unwrap ( );
+        let live_matrix = DW28::get_live_matrix ( &DWInfo::from_str ( "S5028C2012D" ).unwrap ( ) ).await.unwrap ( );
+        assert_eq ! ( live_matrix.holidays, vec ! [ holiday ] );
+
+        // reported, the SET calendar is left unchanged
+        let dates = live_matrix.daily_rows.keys ( ).copied ( ).collect::<Vec<NaiveDate>> ( );
+        assert_eq ! ( crate::calendar::TradingCalendar::set ( ).mismatches ( &live_matrix.holidays, &dates ), vec ! [ holiday ] );
+        assert ! ( crate::calendar::TradingCalendar::set ( ).is_trading_day ( holiday ) );
+
+        let calendar = crate::calendar::TradingCalendar::new ( live_matrix.holidays.iter ( ).copied ( ) );
+        assert ! ( ! calendar.is_trading_day ( holiday ) );
+    }
+
+    #[test]
+    fn test_parse_ric_data ( ) {
+        let ric_data = RicData::parse ( target_html_compressed_hsi_call!().as_str ( ) ).unwrap ( );
+        assert_eq ! ( ric_data.side, DWSide::C );
+        assert_eq ! ( ric_data.exercise_price, Some ( Price::new ( 2700000, 2 ) ) );
+        assert_eq ! ( ric_data.conversion_ratio, Some ( 3125.0 ) );
+        assert_eq ! ( ric_data.underlying_price, Some ( Price::new ( 2626200, 2 ) ) );
+        assert_eq ! ( ric_data.last_trading_date, NaiveDate::from_ymd_opt ( 2020, 12, 30 ) );
+        assert_eq ! ( ric_data.maturity, NaiveDate::from_ymd_opt ( 2021, 1, 7 ) );
+
+        // numbers instead of strings
+        let ric_data = RicData::parse ( target_html_compressed_advanc_call!().as_str ( ) ).unwrap ( );
+        assert_eq ! ( ric_data.conversion_ratio, Some ( 50.13938 ) );
+        assert_eq ! ( ric_data.dividend_yield, Some ( 0.0 ) );
+        assert_eq ! ( ric_data.delta, Some ( 0.17 ) );
+        assert_eq ! ( ric_data.effective_gearing, Some ( 9.3 ) );
+    }
+
+    #[test]
+    fn test_underlying_compared_with_ric_data ( ) {
+        for ( symbol, content ) in [
+            ( "S5028C2012D", target_html_compressed_s50_call!() ),
+            ( "HSI28C2012C", target_html_compressed_hsi_call!() ),
+            ( "HSI28P2101C", target_html_compressed_hsi_put!() ),
+            ( "SPX28P2103A", target_html_compressed_spx_put!() ),
+            ( "ADVA28C2102L", target_html_compressed_advanc_call!() ),
+        ] {
+            let ric_data = RicData::parse ( content.as_str ( ) ).unwrap ( );
+            let underlying = DWInfo::from_str ( symbol ).unwrap ( ).underlying ( );
+            assert_eq ! ( Some ( underlying.ric.clone ( ) ), ric_data.underlying_ric, "{}", symbol );
+            assert_eq ! ( crate::underlying::Underlying::resolve ( ric_data.underlying_ticker.as_deref ( ).unwrap ( ) ), underlying, "{}", symbol );
+        }
+
+        // the same RIC of the aliases
+        assert_eq ! (
+            DW28::get_predicted_dw_ric ( &DWInfo::from_str ( "SET5028C2012D" ).unwrap ( ) ),
+            DW28::get_predicted_dw_ric ( &DWInfo::from_str ( "S5028C2012D" ).unwrap ( ) )
+        );
+        assert_eq ! ( DW28::get_predicted_dw_ric ( &DWInfo::from_str ( "ADVANC28C2102L" ).unwrap ( ) ), "ADVA28C102.BK" );
+    }
+
+    #[test]
+    fn test_find_listed_ric ( ) {
+        let content = target_list_html!();
+        let catalogue = DW28::parse_catalogue ( content.as_str ( ) ).unwrap ( );
+        for ( symbol, ric ) in catalogue.symbols.iter ( ) {
+            assert_eq ! ( DW28::find_listed_ric ( content.as_str ( ), symbol ), ric.as_deref ( ), "{}", symbol );
+        }
+        assert_eq ! ( DW28::find_listed_ric ( content.as_str ( ), "s5028c2012d" ), Some ( "S5028C012D.BK" ) );
+        assert_eq ! ( DW28::find_listed_ric ( content.as_str ( ), "S5028C2012" ), None );
+    }
+
+    #[tokio::test]
+    pub async fn test_get_catalogue ( ) {
+        setup ( );
+        assert_eq ! ( DW28::get_catalogue ( ).await.unwrap ( ).symbols.len ( ), 238 );
+
+        // the DW list not reachable
+        HTML_MAP.with ( |html_map| html_map.borrow_mut ( ).clear ( ) );
+        match DW28::get_catalogue ( ).await {
+            Err ( Error::DataNotFound { symbol, info } ) => {
+                assert_eq ! ( &*symbol, "DW list" );
+                assert ! ( info.unwrap ( ).contains ( DW_LIST_URL!() ) );
+            },
+            other => panic ! ( "Unexpected result: {:?}", other ),
+        }
+    }
+
+    #[tokio::test]
+    pub async fn test_get_live_matrix_not_reachable ( ) {
+        setup ( );
+        let dw_info = DWInfo::from_str ( "S5028C2012D" ).unwrap ( );
+
+        // the price table not reachable
+        match DW28::get_live_matrix ( &dw_info ).await {
+            Err ( Error::DataNotFound { symbol, info } ) => {
+                assert_eq ! ( &*symbol, "S5028C2012D" );
+                assert ! ( info.unwrap ( ).contains ( target_html_compressed_s50_call_url!().as_str ( ) ) );
+            },
+            other => panic ! ( "Unexpected result: {:?}", other ),
+        }
+
+        // the DW list not reachable
+        HTML_MAP.with ( |html_map| html_map.borrow_mut ( ).clear ( ) );
+        match DW28::get_live_matrix ( &dw_info ).await {
+            Err ( Error::DataNotFound { symbol, info } ) => {
+                assert_eq ! ( &*symbol, "DW list" );
+                assert ! ( info.unwrap ( ).contains ( DW_LIST_URL!() ) );
+            },
+            other => panic ! ( "Unexpected result: {:?}", other ),
+        }
+    }
+
+    #[test]
+    fn test_parse_catalogue ( ) {
+        let catalogue = DW28::parse_catalogue ( target_list_html!().as_str ( ) ).unwrap ( );
+        assert_eq ! ( catalogue.broker_id, 28 );
+        assert_eq ! ( catalogue.updated, NaiveDate::from_ymd_opt ( 2020, 12, 23 ).unwrap ( ).and_hms_opt ( 9, 1, 0 ) );
+        assert_eq ! ( catalogue.symbols.len ( ), 238 );
+        assert_eq ! ( catalogue.get_ric ( "S5028C2012D" ), Some ( "S5028C012D.BK" ) );
+        for symbol in [ "S5028C2012D", "ADVA28C2102L", "HSI28C2012C", "HSI28P2101C", "SPX28P2103A" ] {
+            assert_eq ! ( catalogue.validate ( &DWInfo::from_str ( symbol ).unwrap ( ) ), Ok ( ( ) ) );
+        }
+
+        let result = catalogue.validate ( &DWInfo::from_str ( "ADVA28C2102K" ).unwrap ( ) );
+        match result {
+            Err ( Error::UnknownDW { symbol, suggestions } ) => {
+                assert_eq ! ( &*symbol, "ADVA28C2102K" );
+                assert_eq ! ( suggestions.first ( ).map ( |s| &**s ), Some ( "ADVA28C2102L" ) );
+            },
+            _ => panic ! ( "Expected unknown DW: {:?}", result ),
+        }
+
+        assert ! ( matches ! ( DW28::parse_catalogue ( "<html>" ), Err ( Error::FailedParsing { .. } ) ) );
+    }
+
+    #[test]
+    fn test_ric_data_to_expiry ( ) {
+        let calendar = crate::calendar::TradingCalendar::set ( );
+        let today = NaiveDate::from_ymd_opt ( 2020, 12, 23 ).unwrap ( );
+        let ric_data = RicData::parse ( target_html_compressed_hsi_call!().as_str ( ) ).unwrap ( );
+        let expiry = ric_data.to_expiry ( &DWInfo::from_str ( "HSI28C2012C" ).unwrap ( ) ).unwrap ( );
+        assert_eq ! ( ( expiry.year, expiry.month ), ( 2020, 12 ) );
+        assert_eq ! ( expiry.last_trading_date, NaiveDate::from_ymd_opt ( 2020, 12, 30 ) );
+        // 24, 25, 28, 29, 30 Dec
+        assert_eq ! ( expiry.trading_days_to_last_trading ( today, &calendar ), 5 );
+        assert_eq ! ( expiry.days_to_maturity ( today ), Some ( 15 ) );
+        // and 4, 5, 6, 7 Jan
+        assert_eq ! ( expiry.trading_days_to_maturity ( today, &calendar ), Some ( 9 ) );
+        assert ! ( ! expiry.is_expired ( NaiveDate::from_ymd_opt ( 2020, 12, 30 ).unwrap ( ), &calendar ) );
+        assert ! ( expiry.is_expired ( NaiveDate::from_ymd_opt ( 2021, 1, 4 ).unwrap ( ), &calendar ) );
+
+        let ric_data = RicData::parse ( target_html_compressed_advanc_call!().as_str ( ) ).unwrap ( );
+        let expiry = ric_data.to_expiry ( &DWInfo::from_str ( "ADVA28C2102L" ).unwrap ( ) ).unwrap ( );
+        assert_eq ! ( expiry.last_trading_date, NaiveDate::from_ymd_opt ( 2021, 2, 5 ) );
+        assert_eq ! ( expiry.maturity, NaiveDate::from_ymd_opt ( 2021, 2, 11 ) );
+    }
 }
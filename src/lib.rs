@@ -19,7 +19,6 @@ mod testing;
 use std::collections::HashMap;
 use chrono::{
     Duration,
-    Local,
     NaiveDateTime,
     Timelike,
     Weekday,
@@ -112,6 +111,26 @@ pub mod dw28;
 
 pub mod dw06;
 
+pub mod analytics;
+
+pub mod calendar;
+
+pub mod catalogue;
+
+pub mod clock;
+
+pub mod session;
+
+pub mod convention;
+
+pub mod price;
+
+pub mod price_table;
+
+pub mod ticks;
+
+pub mod underlying;
+
 /// # Underlying-price-based underlying-DW price map
 /// 
 /// The underlying and DW price are in f32 type, from original data
@@ -125,13 +144,15 @@ pub struct UnderlyingDWMap <T> {
 impl <T> UnderlyingDWMap <T> {
 }
 
-/// Returns the latest working date of settrade market
+/// Returns the latest working date of settrade market in Asia/Bangkok, skipping the SET holidays.
+/// 
+/// The current time is from [clock::now_bangkok].
 /// 
 /// # See
 /// 
-/// get_working_date_time_from(DateTime)
+/// [calendar::TradingCalendar::get_trading_date_time_from]
 pub fn get_latest_working_date_time ( ) -> NaiveDateTime {
-    get_working_date_time_from ( Local::now ( ).naive_local ( ) )
+    calendar::TradingCalendar::set ( ).get_trading_date_time_from ( clock::now_bangkok ( ).naive_local ( ) )
 }
 
 /// Returns the working date/time nearest to given DateTime.get_latest_working_date_time()
@@ -144,6 +165,8 @@ pub fn get_latest_working_date_time ( ) -> NaiveDateTime {
 /// 
 /// Otherwise; If given DateTime base is in Sat-Sun, the next Mon is returned.
 /// 
+/// Holidays are not skipped, see [calendar::TradingCalendar::get_trading_date_time_from].
+/// 
 /// # Arguments
 /// 
 /// * `datetime` - A DateTime<Local> object as the base date/time.
@@ -169,81 +192,34 @@ pub fn get_working_date_time_from ( mut datetime: NaiveDateTime ) -> NaiveDateTi
 
 pub mod instrument {
     use super::*;
+    use crate::price::Price;
     
-    /// Returns upper adjacent price.
-    /// 
-    /// The range is in following:
-    /// 	0.00-1.99	0.01
-    /// 	2.00-4.98	0.02
-    /// 	5.00-9.95	0.05
-    /// 	10.00-24.90	0.10
-    /// 	25.00-99.75	0.25
-    /// 	100.00-199.50	0.50
-    /// 	200.00-399.00	1.00
-    /// 	400.00-upper	2.00
+    /// Returns lower adjacent price, by [crate::ticks::SET_EQUITY].
     /// 
     /// # Arguments
     /// 
-    /// * `price` - Price to be converted.
+    /// * `price` - Price to be converted, in i32 format based on [DEFAULT_PRICE_DIGIT].
     pub fn to_lower_adjacent_price ( price: i32 ) -> i32 {
-        price -
-            if price <= 200 {
-                1
-            } else if price <= 500 {
-                2
-            } else if price <= 1000 {
-                5
-            } else if price <= 2500 {
-                10
-            } else if price <= 10000 {
-                25
-            } else if price <= 20000 {
-                50
-            } else if price <= 40000 {
-                100
-            } else {
-                200
-            }
+        crate::ticks::SET_EQUITY.step ( Price::from_int_price ( price, DEFAULT_PRICE_DIGIT ), -1 )
+            .map ( |price| price.to_int_price ( DEFAULT_PRICE_DIGIT ) )
+            .unwrap_or ( 0 )
     }
 
-    /// Returns upper adjacent price.
-    /// 
-    /// The range is in following:
-    /// 	000-199	1
-    /// 	200-498	2
-    /// 	500-995	5
-    /// 	1000-2490	10
-    /// 	2500-9975	25
-    /// 	10000-19950	50
-    /// 	20000-39900	100
-    /// 	40000-upper	200
+    /// Returns upper adjacent price, by [crate::ticks::SET_EQUITY].
     /// 
     /// # Arguments
     /// 
-    /// * `price` - Price to be converted.
+    /// * `price` - Price to be converted, in i32 format based on [DEFAULT_PRICE_DIGIT].
     pub fn to_upper_adjacent_price ( price: i32 ) -> i32 {
-        price +
-            if price < 200 {
-                1
-            } else if price < 500 {
-                2
-            } else if price < 1000 {
-                5
-            } else if price < 2500 {
-                10
-            } else if price < 10000 {
-                25
-            } else if price < 20000 {
-                50
-            } else if price < 40000 {
-                100
-            } else {
-                200
-            }
+        crate::ticks::SET_EQUITY.step ( Price::from_int_price ( price, DEFAULT_PRICE_DIGIT ), 1 )
+            .map ( |price| price.to_int_price ( DEFAULT_PRICE_DIGIT ) )
+            .unwrap_or ( 0 )
     }
     
     /// Returns the i32-formatted price, based on given [price_digit]
     /// 
+    /// Prefer [crate::price::Price::to_int_price] if the price can be parsed from the original string.
+    /// 
     /// # Arguments
     /// 
     /// * `price` - Price in f32
@@ -322,6 +298,16 @@ pub mod instrument {
         use async_trait::async_trait;
         use chrono::NaiveDate;
         use super::*;
+        use crate::price_table::PriceTable;
+        use crate::calendar::TradingCalendar;
+        use crate::underlying::{
+            AssetClass,
+            Underlying,
+        };
+        use std::{
+            fmt,
+            str::FromStr,
+        };
         /*
         use std::pin::Pin;
         use futures::future::Future;
@@ -348,11 +334,25 @@ pub mod instrument {
             
             /// Returns the map to underlying-DW prices.get_latest_working_date_time()
             /// 
+            /// The underlying prices are in i32 format based on [crate::underlying::Underlying::price_digits],
+            /// e.g. 0 for HSI points.
+            /// 
             /// # Arguments
             /// 
             /// * `underlying_symbol` - Underlying symbol
             //async fn get_underlying_dw_price_table ( dw_info: &dw::DWInfo ) -> Self::TableResult;
             async fn get_underlying_dw_price_table ( dw_info: &dw::DWInfo ) -> Result<HashMap<Self::UnderlyingType, Vec<Self::DWType>>, Error>;
+
+            /// Returns the price table with the date of each column, and the ask side if published.
+            /// 
+            /// By default, the price table is not supported.
+            /// 
+            /// # Arguments
+            /// 
+            /// * `dw_info` - DW info
+            async fn get_price_table ( dw_info: &dw::DWInfo ) -> Result<PriceTable, Error> {
+                Err ( Error::UnsupportedDWTableScraping { broker_id: dw_info.broker_id } )
+            }
             //async fn get_underlying_dw_price_table ( dw_info: &dw::DWInfo ) -> Option<HashMap<U, Vec<D>>>;
             //fn get_underlying_dw_price_table ( dw_info: &dw::DWInfo ) -> Pin<Box<dyn Future<Output = Result<HashMap<U, Vec<D>>, ()>> + Send>>;
             //fn get_underlying_dw_price_table ( dw_info: &dw::DWInfo ) -> dyn Future<Output = Option<HashMap<U, Vec<D>>>> + '_;
@@ -428,6 +428,9 @@ pub mod instrument {
             #[snafu(display("Unsupported DW table scraping: {}", "broker_id"))]
             UnsupportedDWTableScraping{broker_id: u8},
             
+            #[snafu(display("Unknown or expired DW: {}, close matches: {:?}", symbol, suggestions))]
+            UnknownDW{symbol: Box<str>, suggestions: Vec<Box<str>>},
+            
             #[snafu(display("Test"))]
             Test,
         }
@@ -443,13 +446,38 @@ pub mod instrument {
             pairs: Vec<(U, D)>,
         }
 
+        /// Error of parsing a symbol in the DW naming scheme. See [DWInfo].
+        #[derive(Debug, PartialEq, Eq, Clone, Snafu)]
+        pub enum ParseDWSymbolError {
+            #[snafu(display("Invalid character {:?} at {} of symbol: {}", found, position, symbol))]
+            InvalidCharacter{symbol: Box<str>, position: usize, found: char},
+
+            #[snafu(display("Missing series letters at the end of symbol: {}", symbol))]
+            MissingSeries{symbol: Box<str>},
+
+            #[snafu(display("Invalid expiry of symbol, expected 4 digits of YYMM: {}", symbol))]
+            InvalidExpiry{symbol: Box<str>},
+
+            #[snafu(display("Invalid side {:?} of symbol, expected one of {:?}: {}", found, expected, symbol))]
+            InvalidSide{symbol: Box<str>, found: Option<char>, expected: &'static str},
+
+            #[snafu(display("Invalid broker id of symbol, expected 2 digits: {}", symbol))]
+            InvalidBrokerId{symbol: Box<str>},
+
+            #[snafu(display("Missing underlying of symbol: {}", symbol))]
+            MissingUnderlying{symbol: Box<str>},
+        }
+
         /// DW Info from symbol
         /// 
-        /// - underlying symbol. Up to 4 chars.
-        /// - broker id. 2 chars.
+        /// - underlying symbol. Letters and digits, e.g. "S50", "SET50", "2S".
+        /// - broker id. 2 digits.
         /// - side. 'C' or 'P'. See [DWSide]
         /// - expiration date. YYMM format.
-        /// - series
+        /// - series. 1 or more letters.
+        /// 
+        /// The symbol is parsed from its end, so the digits of the underlying symbol are not taken
+        /// as the broker id. Parse with [std::str::FromStr], and format back with [std::fmt::Display].
         #[derive(PartialEq, Clone, Debug)]
         pub struct DWInfo {
             pub symbol: Box<str>,
@@ -457,7 +485,99 @@ pub mod instrument {
             pub broker_id: u8,
             pub side: DWSide,
             pub expire_yymm: [u8; 4],
-            pub series: char,
+            pub series: Box<str>,
+        }
+
+        /// Expiry of a DW, from the YYMM of its symbol, with the exact dates if known from the
+        /// issuer's terms, e.g. [crate::dw28::RicData::to_expiry].
+        #[derive(PartialEq, Eq, Clone, Copy, Debug)]
+        pub struct DWExpiry {
+            pub year: i32,
+            pub month: u32,
+            pub last_trading_date: Option<NaiveDate>,
+            pub maturity: Option<NaiveDate>,
+        }
+
+        impl DWExpiry {
+            /// Returns the expiry with given dates from the issuer's terms.
+            /// 
+            /// # Arguments
+            /// 
+            /// * `last_trading_date` - Last trading date.
+            /// * `maturity` - Maturity date.
+            pub fn with_terms ( self, last_trading_date: Option<NaiveDate>, maturity: Option<NaiveDate> ) -> Self {
+                DWExpiry {
+                    last_trading_date,
+                    maturity,
+                    ..self
+                }
+            }
+
+            /// Returns the last date of the expiry month.
+            pub fn month_end ( &self ) -> NaiveDate {
+                let ( year, month ) = if self.month == 12 { ( self.year + 1, 1 ) } else { ( self.year, self.month + 1 ) };
+                NaiveDate::from_ymd_opt ( year, month, 1 )
+                    .and_then ( |date| date.pred_opt ( ) )
+                    .expect ( "Invalid expiry month" )
+            }
+
+            /// Returns the last trading date, or the last trading day of the expiry month if not known,
+            /// as the latest possible one.
+            /// 
+            /// # Arguments
+            /// 
+            /// * `calendar` - Trading calendar.
+            pub fn last_trading_date_or_estimate ( &self, calendar: &TradingCalendar ) -> NaiveDate {
+                self.last_trading_date.unwrap_or_else ( || {
+                    let month_end = self.month_end ( );
+                    if calendar.is_trading_day ( month_end ) {
+                        month_end
+                    } else {
+                        calendar.previous_trading_day ( month_end )
+                    }
+                } )
+            }
+
+            /// Returns true if given date is after the last trading date. See [DWExpiry::last_trading_date_or_estimate].
+            /// 
+            /// # Arguments
+            /// 
+            /// * `today` - Date to check.
+            /// * `calendar` - Trading calendar.
+            pub fn is_expired ( &self, today: NaiveDate, calendar: &TradingCalendar ) -> bool {
+                today > self.last_trading_date_or_estimate ( calendar )
+            }
+
+            /// Returns the trading days after given date up to and including the last trading date.
+            /// See [DWExpiry::last_trading_date_or_estimate].
+            /// 
+            /// # Arguments
+            /// 
+            /// * `today` - Date to count from.
+            /// * `calendar` - Trading calendar.
+            pub fn trading_days_to_last_trading ( &self, today: NaiveDate, calendar: &TradingCalendar ) -> i64 {
+                calendar.trading_days_between ( today, self.last_trading_date_or_estimate ( calendar ) )
+            }
+
+            /// Returns the calendar days from given date to the maturity, or None if the maturity is not known.
+            /// 
+            /// # Arguments
+            /// 
+            /// * `today` - Date to count from.
+            pub fn days_to_maturity ( &self, today: NaiveDate ) -> Option<i64> {
+                self.maturity.map ( |maturity| ( maturity - today ).num_days ( ) )
+            }
+
+            /// Returns the trading days after given date up to and including the maturity, or None if
+            /// the maturity is not known.
+            /// 
+            /// # Arguments
+            /// 
+            /// * `today` - Date to count from.
+            /// * `calendar` - Trading calendar.
+            pub fn trading_days_to_maturity ( &self, today: NaiveDate, calendar: &TradingCalendar ) -> Option<i64> {
+                self.maturity.map ( |maturity| calendar.trading_days_between ( today, maturity ) )
+            }
         }
 
         #[derive(PartialEq, Clone, Debug)]
@@ -469,52 +589,212 @@ pub mod instrument {
 
         impl DWInfo {
 
-            /// Returns option of [DWInfo] by parsing given [symbol].
+            /// Returns the expiry year and month of [DWInfo::expire_yymm], without the exact dates.
+            /// 
+            /// Returns None if it is not a valid YYMM.
+            pub fn expiry ( &self ) -> Option<DWExpiry> {
+                to_expiry ( &self.expire_yymm )
+            }
+
+            /// Validates the DW against the cached catalogue of its issuer, to be called before
+            /// requesting the price table. See [crate::catalogue].
+            /// 
+            /// Returns [Error::UnknownDW] with the close matches if not listed, or Ok if the catalogue
+            /// of the issuer is not cached.
+            pub fn validate ( &self ) -> Result<( ), Error> {
+                match crate::catalogue::get_catalogue ( self.broker_id ) {
+                    Some ( catalogue ) => catalogue.validate ( self ),
+                    None => Ok ( ( ) ),
+                }
+            }
+
+            /// Returns the underlying resolved from [DWInfo::underlying_symbol].
+            pub fn underlying ( &self ) -> Underlying {
+                Underlying::resolve ( &self.underlying_symbol )
+            }
+
+            /// Returns true if the underlying is the SET50 index, e.g. "S50" or "SET50".
+            pub fn is_set50 ( &self ) -> bool {
+                self.underlying ( ).asset_class == AssetClass::Set50Index
+            }
+        }
+
+        /// Parts of a symbol in the SET DW naming scheme, borrowed from the symbol.
+        #[derive(PartialEq, Eq, Clone, Copy, Debug)]
+        pub(crate) struct SymbolParts<'a> {
+            pub underlying_symbol: &'a str,
+            pub broker_id: u8,
+            pub side: char,
+            pub expire_yymm: [u8; 4],
+            pub series: &'a str,
+        }
+
+        /// Returns the parts of given symbol, parsed from its end, so the digits of the underlying
+        /// symbol are not taken as the broker id.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `symbol` - Symbol to be parsed.
+        /// * `sides` - Side letters of the product, e.g. "CP" of DWs.
+        pub(crate) fn parse_symbol_parts<'a> ( symbol: &'a str, sides: &'static str ) -> Result<SymbolParts<'a>, ParseDWSymbolError> {
+            let boxed = || symbol.to_owned ( ).into_boxed_str ( );
+            if let Some ( ( position, found ) ) = symbol.char_indices ( )
+                    .find ( |( _, c )| ! c.is_ascii_uppercase ( ) && ! c.is_ascii_digit ( ) ) {
+                return Err ( ParseDWSymbolError::InvalidCharacter { symbol: boxed ( ), position, found } );
+            }
+
+            // all ASCII from here
+            let bytes = symbol.as_bytes ( );
+            let is_side = |b: Option<&u8>| b.is_some_and ( |b| sides.as_bytes ( ).contains ( b ) );
+            let series_start = bytes.iter ( ).rposition ( |b| ! b.is_ascii_uppercase ( ) ).map_or ( 0, |i| i + 1 );
+            if series_start == bytes.len ( ) {
+                return Err ( ParseDWSymbolError::MissingSeries { symbol: boxed ( ) } );
+            }
+            let digits_start = bytes [ ..series_start ].iter ( ).rposition ( |b| ! b.is_ascii_digit ( ) ).map_or ( 0, |i| i + 1 );
+            let expire_start = match series_start - digits_start {
+                4 => digits_start,
+                _ => return Err ( ParseDWSymbolError::InvalidExpiry { symbol: boxed ( ) } ),
+            };
+            let side = match expire_start.checked_sub ( 1 ).map ( |i| &bytes [ i ] ) {
+                Some ( side ) if is_side ( Some ( side ) ) => char::from ( *side ),
+                found => return Err ( ParseDWSymbolError::InvalidSide { symbol: boxed ( ), found: found.map ( |b| char::from ( *b ) ), expected: sides } ),
+            };
+            let broker_start = match ( expire_start - 1 ).checked_sub ( 2 ) {
+                Some ( i ) if bytes [ i..i + 2 ].iter ( ).all ( u8::is_ascii_digit ) => i,
+                _ => return Err ( ParseDWSymbolError::InvalidBrokerId { symbol: boxed ( ) } ),
+            };
+            if broker_start == 0 {
+                return Err ( ParseDWSymbolError::MissingUnderlying { symbol: boxed ( ) } );
+            }
+
+            let mut expire_yymm = [0u8; 4];
+            expire_yymm.copy_from_slice ( &bytes [ expire_start..series_start ] );
+            Ok ( SymbolParts {
+                underlying_symbol: &symbol [ ..broker_start ],
+                broker_id: symbol [ broker_start..broker_start + 2 ].parse::<u8> ( ).expect ( "Failed to parse the digits of broker id" ),
+                side,
+                expire_yymm,
+                series: &symbol [ series_start.. ],
+            } )
+        }
+
+        /// Returns the expiry year and month of given YYMM, without the exact dates.
+        /// 
+        /// Returns None if it is not a valid YYMM.
+        pub(crate) fn to_expiry ( expire_yymm: &[u8; 4] ) -> Option<DWExpiry> {
+            let yymm = std::str::from_utf8 ( expire_yymm ).ok ( )?;
+            let year = 2000 + yymm.get ( 0..2 )?.parse::<i32> ( ).ok ( )?;
+            let month = yymm.get ( 2..4 )?.parse::<u32> ( ).ok ( )
+                .filter ( |month| ( 1..=12 ).contains ( month ) )?;
+            Some ( DWExpiry {
+                year,
+                month,
+                last_trading_date: None,
+                maturity: None,
+            } )
+        }
+
+        impl FromStr for DWInfo {
+            type Err = ParseDWSymbolError;
+
+            /// Returns [DWInfo] by parsing given symbol.
             /// 
             /// # Arguments
             /// 
             /// * `dw_symbol` - DW symbol to be parsed.
-            pub fn from_str ( dw_symbol: &str ) -> Option<Self> {
-                let regex = Regex::new ( r#"(\d{2})([CP])(\d{4})"# )
-                    .expect ( "Failed to create Regex of DW symbol.");
-                
-                let captures = regex.captures ( dw_symbol );
-                
-                if let Some ( captures ) = captures {
-                    let captured_broker_id = captures.get ( 0 ).unwrap ( );
-                    if captured_broker_id.start ( ) <= 0 ||
-                            captured_broker_id.end ( ) < dw_symbol.len ( ) - 1 {
-                        return None;
-                    }
+            fn from_str ( dw_symbol: &str ) -> Result<Self, Self::Err> {
+                DWInfoRef::parse ( dw_symbol ).map ( DWInfo::from )
+            }
+        }
+
+        /// [DWInfo] borrowed from the parsed symbol, without allocation, e.g. to filter symbols
+        /// from exchange feeds before keeping some as [DWInfo].
+        #[derive(PartialEq, Clone, Debug)]
+        pub struct DWInfoRef<'a> {
+            pub symbol: &'a str,
+            pub underlying_symbol: &'a str,
+            pub broker_id: u8,
+            pub side: DWSide,
+            pub expire_yymm: [u8; 4],
+            pub series: &'a str,
+        }
+
+        impl<'a> DWInfoRef<'a> {
+            /// Returns [DWInfoRef] by parsing given symbol, the same as [DWInfo::from_str].
+            /// 
+            /// Allocates only the symbol of the error if failed.
+            /// 
+            /// # Arguments
+            /// 
+            /// * `dw_symbol` - DW symbol to be parsed.
+            pub fn parse ( dw_symbol: &'a str ) -> Result<Self, ParseDWSymbolError> {
+                let parts = parse_symbol_parts ( dw_symbol, "CP" )?;
+                Ok ( DWInfoRef {
+                    symbol: dw_symbol,
+                    underlying_symbol: parts.underlying_symbol,
+                    broker_id: parts.broker_id,
+                    side: if parts.side == 'C' { DWSide::C } else { DWSide::P },
+                    expire_yymm: parts.expire_yymm,
+                    series: parts.series,
+                } )
+            }
+
+            /// Returns the expiry year and month, without the exact dates. See [DWInfo::expiry].
+            pub fn expiry ( &self ) -> Option<DWExpiry> {
+                to_expiry ( &self.expire_yymm )
+            }
+        }
 
-                    //std::panic::catch_unwind ( || {
-                        let mut expire = [0u8; 4];
-                        expire.copy_from_slice(captures.get ( 3 ).unwrap ( ).as_str ( ).as_bytes() );
-
-                        Some ( DWInfo {
-                            symbol: dw_symbol.clone ( ).to_owned ( ).into_boxed_str ( ),
-                            underlying_symbol: dw_symbol.get ( 0..captured_broker_id.start ( ) ).unwrap ( ).to_owned ( ).into_boxed_str ( ),
-                            broker_id: captures.get ( 1 ).unwrap ( ).as_str ( ).parse::<u8> ( ).unwrap ( ),
-                            side: match captures.get ( 2 ).unwrap ( ).as_str ( ) {
-                                "C" => DWSide::C,
-                                "P" => DWSide::P,
-                                _ => DWSide::Unknown,
-                            },
-                            expire_yymm: expire,
-                            series: dw_symbol.chars ( ).nth ( captured_broker_id.end ( ) ).unwrap ( ),
-                        } )
-                    //} ).unwrap_or ( None )
-                } else {
-                    None
+        impl From<DWInfoRef<'_>> for DWInfo {
+            fn from ( dw_info: DWInfoRef<'_> ) -> Self {
+                DWInfo {
+                    symbol: dw_info.symbol.into ( ),
+                    underlying_symbol: dw_info.underlying_symbol.into ( ),
+                    broker_id: dw_info.broker_id,
+                    side: dw_info.side,
+                    expire_yymm: dw_info.expire_yymm,
+                    series: dw_info.series.into ( ),
                 }
             }
         }
+
+        impl<'a> From<&'a DWInfo> for DWInfoRef<'a> {
+            fn from ( dw_info: &'a DWInfo ) -> Self {
+                DWInfoRef {
+                    symbol: &dw_info.symbol,
+                    underlying_symbol: &dw_info.underlying_symbol,
+                    broker_id: dw_info.broker_id,
+                    side: dw_info.side.clone ( ),
+                    expire_yymm: dw_info.expire_yymm,
+                    series: &dw_info.series,
+                }
+            }
+        }
+
+        impl fmt::Display for DWInfo {
+            /// Formats the symbol from the parts, the same as [DWInfo::symbol] if parsed.
+            fn fmt ( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result {
+                write ! ( f, "{}{:02}{}{}{}",
+                    self.underlying_symbol,
+                    self.broker_id,
+                    match self.side {
+                        DWSide::C => 'C',
+                        DWSide::P => 'P',
+                        DWSide::Unknown => '?',
+                    },
+                    String::from_utf8_lossy ( &self.expire_yymm ),
+                    self.series,
+                )
+            }
+        }
         
         #[async_trait(?Send)]
         impl DWPriceTable for DWInfo {
             type UnderlyingType = i32;
             type DWType = f32;
 
+            /// Returns the map of the issuer of given DW, normalized to [crate::convention::QuoteConvention::NORMALIZED]
+            /// by the issuer module, keyed in [crate::underlying::Underlying::price_digits].
             async fn get_underlying_dw_price_table(dw_info: &Self) -> Result<HashMap<i32, Vec<f32>>, Error> {
                 match dw_info.broker_id {
                     6   => dw06::DW06::get_underlying_dw_price_table(dw_info).await,
@@ -523,6 +803,17 @@ pub mod instrument {
                     _   => Err ( Error::UnsupportedDWTableScraping { broker_id: dw_info.broker_id.into() } )
                 }
             }
+
+            /// Returns the table of the issuer of given DW, normalized to [crate::convention::QuoteConvention::NORMALIZED]
+            /// by the issuer module.
+            async fn get_price_table(dw_info: &Self) -> Result<PriceTable, Error> {
+                match dw_info.broker_id {
+                    6   => dw06::DW06::get_price_table(dw_info).await,
+                    13  => dw13::DW13::get_price_table(dw_info).await,
+                    28  => dw28::DW28::get_price_table(dw_info).await,
+                    _   => Err ( Error::UnsupportedDWTableScraping { broker_id: dw_info.broker_id } )
+                }
+            }
         }
         
         #[allow(non_snake_case)]
@@ -553,6 +844,37 @@ pub mod instrument {
                 };
             }
             
+            #[test]
+            fn givenDWSymbol_whenGetExpiry_thenGotYearAndMonth ( ) {
+                let expiry = DWInfo::from_str ( "S5028C2012D" ).unwrap ( ).expiry ( ).unwrap ( );
+                assert_eq ! ( ( expiry.year, expiry.month ), ( 2020, 12 ) );
+                assert_eq ! ( expiry.last_trading_date, None );
+                assert_eq ! ( expiry.maturity, None );
+                assert_eq ! ( expiry.month_end ( ), NaiveDate::from_ymd_opt ( 2020, 12, 31 ).unwrap ( ) );
+
+                let mut dw_info = DWInfo::from_str ( "S5028C2012D" ).unwrap ( );
+                dw_info.expire_yymm = *b"2013";
+                assert_eq ! ( dw_info.expiry ( ), None );
+            }
+
+            #[test]
+            fn givenExpiryWithoutTerms_whenGetDaysToExpiry_thenEstimatedByMonthEnd ( ) {
+                let calendar = TradingCalendar::set ( );
+                let today = NaiveDate::from_ymd_opt ( 2020, 12, 23 ).unwrap ( );
+                let expiry = DWInfo::from_str ( "S5028C2012D" ).unwrap ( ).expiry ( ).unwrap ( );
+
+                // 31 Dec is a holiday
+                assert_eq ! ( expiry.last_trading_date_or_estimate ( &calendar ), NaiveDate::from_ymd_opt ( 2020, 12, 30 ).unwrap ( ) );
+                assert_eq ! ( expiry.trading_days_to_last_trading ( today, &calendar ), 5 );
+                assert_eq ! ( expiry.days_to_maturity ( today ), None );
+                assert_eq ! ( expiry.trading_days_to_maturity ( today, &calendar ), None );
+
+                let expiry = expiry.with_terms ( NaiveDate::from_ymd_opt ( 2020, 12, 29 ), NaiveDate::from_ymd_opt ( 2021, 1, 6 ) );
+                assert_eq ! ( expiry.trading_days_to_last_trading ( today, &calendar ), 4 );
+                assert_eq ! ( expiry.days_to_maturity ( today ), Some ( 14 ) );
+                assert ! ( expiry.is_expired ( NaiveDate::from_ymd_opt ( 2020, 12, 30 ).unwrap ( ), &calendar ) );
+            }
+
             #[tokio::test]
             async fn givenDW13Symbol_whenGetPriceTable_thenGotResultSameAsFromDW13Struct ( ) {
                 setup ( );
@@ -640,7 +962,7 @@ pub mod instrument {
                     broker_id: 0,
                     side: DWSide::C,
                     expire_yymm: to_fixed_u8_arr! ( "3333", 4 ),
-                    series: 'Z',
+                    series: "Z".into ( ),
                 };
                 
                 let price_table = DWInfo::get_underlying_dw_price_table( &dw_info ).await;
@@ -648,70 +970,70 @@ pub mod instrument {
             }
             
             #[test]
-            fn givenPutDWSymbol_whenFromStr_thenGotSomeDWInfo ( ) {
+            fn givenPutDWSymbol_whenFromStr_thenGotOkDWInfo ( ) {
                 assert_eq ! ( DWInfo::from_str ( "ABCD00P5678A" ),
-                    Some ( DWInfo {
+                    Ok ( DWInfo {
                         symbol: "ABCD00P5678A".to_owned ( ).into_boxed_str ( ),
                         underlying_symbol: "ABCD".to_owned ( ).into_boxed_str ( ),
                         broker_id: 0,
                         side: DWSide::P,
                         expire_yymm: to_fixed_u8_arr! ( "5678", 4 ),
-                        series: 'A',
+                        series: "A".into ( ),
                     } )
                 );
             }
 
             #[test]
-            fn givenCallDWSymbol_whenFromStr_thenGotSomeDWInfo ( ) {
+            fn givenCallDWSymbol_whenFromStr_thenGotOkDWInfo ( ) {
                 assert_eq ! ( DWInfo::from_str ( "VVVV00C5678A" ),
-                    Some ( DWInfo {
+                    Ok ( DWInfo {
                         symbol: "VVVV00C5678A".to_owned ( ).into_boxed_str ( ),
                         underlying_symbol: "VVVV".to_owned ( ).into_boxed_str ( ),
                         broker_id: 0,
                         side: DWSide::C,
                         expire_yymm: to_fixed_u8_arr! ( "5678", 4 ),
-                        series: 'A',
+                        series: "A".into ( ),
                     } )
                 );
             }
 
             #[test]
-            fn givenDWSymbolWithShortName_whenFromStr_thenGotSomeDWInfo ( ) {
+            fn givenDWSymbolWithShortName_whenFromStr_thenGotOkDWInfo ( ) {
                 assert_eq ! ( DWInfo::from_str ( "CC00C2020A" ),
-                    Some ( DWInfo {
+                    Ok ( DWInfo {
                         symbol: "CC00C2020A".to_owned ( ).into_boxed_str ( ),
                         underlying_symbol: "CC".to_owned ( ).into_boxed_str ( ),
                         broker_id: 0,
                         side: DWSide::C,
                         expire_yymm: to_fixed_u8_arr! ( "2020", 4 ),
-                        series: 'A',
+                        series: "A".into ( ),
                     } )
                 );
 
                 assert_eq ! ( DWInfo::from_str ( "XX00C3333Z" ),
-                    Some ( DWInfo {
+                    Ok ( DWInfo {
                         symbol: "XX00C3333Z".to_owned ( ).into_boxed_str ( ),
                         underlying_symbol: "XX".to_owned ( ).into_boxed_str ( ),
                         broker_id: 0,
                         side: DWSide::C,
                         expire_yymm: to_fixed_u8_arr! ( "3333", 4 ),
-                        series: 'Z',
+                        series: "Z".into ( ),
                     } )
                 );
             }
 
             #[test]
-            fn givenUnknownDWType_whenFromStr_thenNone ( ) {
+            fn givenUnknownDWType_whenFromStr_thenErr ( ) {
                 assert_eq ! ( DWInfo::from_str ( "AA00X5555Y" ),
-                    None
+                    Err ( ParseDWSymbolError::InvalidSide { symbol: "AA00X5555Y".into ( ), found: Some ( 'X' ), expected: "CP" } )
                     // currently, no support for unknown type
                     /*
-                    Some ( DWInfo {
+                    Ok ( DWInfo {
                         symbol: "AA".to_owned ( ).into_boxed_str ( ),
                         broker_id: 0,
                         side: DWSide::Unknown,
                         expire_yymm: to_fixed_u8_arr! ( "5555", 4 ),
-                        series: 'Y',
+                        series: "Y".into ( ),
                     } )
                     */
                 );
@@ -721,56 +1043,127 @@ pub mod instrument {
             fn givenTooLongBrokerIdSize_whenFromStr_thenDWInfoAsExceedingIdIsAPartOfUnderlyingSymbol ( ) {
                 // broker sz=1
                 assert_eq ! ( DWInfo::from_str ( "WW333C0000Z" ),
-                    Some ( DWInfo {
+                    Ok ( DWInfo {
                         symbol: "WW333C0000Z".to_owned ( ).into_boxed_str ( ),
                         underlying_symbol: "WW3".to_owned ( ).into_boxed_str ( ),
                         broker_id: 33,
                         side: DWSide::C,
                         expire_yymm: to_fixed_u8_arr! ( "0000", 4 ),
-                        series: 'Z',
+                        series: "Z".into ( ),
                     } )
                 );
             }
 
             #[test]
-            fn givenTooShortBrokerIdSize_whenFromStr_thenNone ( ) {
+            fn givenTooShortBrokerIdSize_whenFromStr_thenErr ( ) {
                 // broker sz=1
                 assert_eq ! ( DWInfo::from_str ( "WW1C0000Z" ),
-                    None
+                    Err ( ParseDWSymbolError::InvalidBrokerId { symbol: "WW1C0000Z".into ( ) } )
                 );
             }
 
             #[test]
-            fn givenNoBrokerIdSize_whenFromStr_thenNone ( ) {
+            fn givenNoBrokerIdSize_whenFromStr_thenErr ( ) {
                 // broker sz=0
                 assert_eq ! ( DWInfo::from_str ( "QQC0000Z" ),
-                    None
+                    Err ( ParseDWSymbolError::InvalidBrokerId { symbol: "QQC0000Z".into ( ) } )
                 );
             }
 
             #[test]
-            fn givenNoUnderlyingPart_whenFromStr_thenNone ( ) {
+            fn givenNoUnderlyingPart_whenFromStr_thenErr ( ) {
                 // no underlying part
                 assert_eq ! ( DWInfo::from_str ( "00C0000Z" ),
-                    None
+                    Err ( ParseDWSymbolError::MissingUnderlying { symbol: "00C0000Z".into ( ) } )
                 );
             }
 
             #[test]
-            fn givenTooShortExpiryDateSize_whenFromStr_thenNone ( ) {
+            fn givenTooShortExpiryDateSize_whenFromStr_thenErr ( ) {
                 // expiry date size < 4
                 assert_eq ! ( DWInfo::from_str ( "EE00C123Z" ),
-                    None
+                    Err ( ParseDWSymbolError::InvalidExpiry { symbol: "EE00C123Z".into ( ) } )
                 );
             }
 
             #[test]
-            fn givenTooLongExpiryDateSize_whenFromStr_thenNone ( ) {
+            fn givenTooLongExpiryDateSize_whenFromStr_thenErr ( ) {
                 // expiry date size > 4
                 assert_eq ! ( DWInfo::from_str ( "EE00C12345Z" ),
-                    None
+                    Err ( ParseDWSymbolError::InvalidExpiry { symbol: "EE00C12345Z".into ( ) } )
+                );
+
+                // without a side before the digits
+                assert_eq ! ( DWInfo::from_str ( "EE00X12345Z" ),
+                    Err ( ParseDWSymbolError::InvalidExpiry { symbol: "EE00X12345Z".into ( ) } )
                 );
             }
+
+            #[test]
+            fn givenNumericUnderlyingAndMultiLetterSeries_whenFromStr_thenGotOkDWInfo ( ) {
+                let dw_info = DWInfo::from_str ( "2S13C2106AB" ).unwrap ( );
+                assert_eq ! ( &*dw_info.underlying_symbol, "2S" );
+                assert_eq ! ( dw_info.broker_id, 13 );
+                assert_eq ! ( dw_info.side, DWSide::C );
+                assert_eq ! ( &dw_info.expire_yymm, b"2106" );
+                assert_eq ! ( &*dw_info.series, "AB" );
+
+                let dw_info = DWInfo::from_str ( "SET5028P2103A" ).unwrap ( );
+                assert_eq ! ( &*dw_info.underlying_symbol, "SET50" );
+                assert_eq ! ( dw_info.broker_id, 28 );
+                assert ! ( dw_info.is_set50 ( ) );
+                assert ! ( DWInfo::from_str ( "S5028C2012D" ).unwrap ( ).is_set50 ( ) );
+                assert ! ( ! DWInfo::from_str ( "S50X28C2012D" ).unwrap ( ).is_set50 ( ) );
+            }
+
+            #[test]
+            fn givenTrailingOrInvalidCharacters_whenFromStr_thenErr ( ) {
+                assert_eq ! ( DWInfo::from_str ( "S5028C2012D " ),
+                    Err ( ParseDWSymbolError::InvalidCharacter { symbol: "S5028C2012D ".into ( ), position: 11, found: ' ' } )
+                );
+                assert_eq ! ( DWInfo::from_str ( "s5028C2012D" ),
+                    Err ( ParseDWSymbolError::InvalidCharacter { symbol: "s5028C2012D".into ( ), position: 0, found: 's' } )
+                );
+                assert_eq ! ( DWInfo::from_str ( "S5028C2012D1" ),
+                    Err ( ParseDWSymbolError::MissingSeries { symbol: "S5028C2012D1".into ( ) } )
+                );
+                assert_eq ! ( DWInfo::from_str ( "" ),
+                    Err ( ParseDWSymbolError::MissingSeries { symbol: "".into ( ) } )
+                );
+                assert_eq ! ( DWInfo::from_str ( "C2012D" ),
+                    Err ( ParseDWSymbolError::InvalidBrokerId { symbol: "C2012D".into ( ) } )
+                );
+                assert_eq ! ( DWInfo::from_str ( "2012D" ),
+                    Err ( ParseDWSymbolError::InvalidSide { symbol: "2012D".into ( ), found: None, expected: "CP" } )
+                );
+                assert_eq ! (
+                    DWInfo::from_str ( "AA00X5555Y" ).unwrap_err ( ).to_string ( ),
+                    "Invalid side Some('X') of symbol, expected one of \"CP\": AA00X5555Y"
+                );
+            }
+
+            #[test]
+            fn givenDWSymbol_whenDisplay_thenGotSameSymbol ( ) {
+                for symbol in [ "S5028C2012D", "SET5028P2103A", "2S13C2106AB", "WW333C0000Z", "HSI06C2101B" ] {
+                    let dw_info = symbol.parse::<DWInfo> ( ).unwrap ( );
+                    assert_eq ! ( dw_info.to_string ( ), symbol );
+                    assert_eq ! ( dw_info.to_string ( ).parse::<DWInfo> ( ), Ok ( dw_info ) );
+                }
+            }
+
+            #[test]
+            fn givenDWSymbol_whenParseDWInfoRef_thenGotSamePartsAsDWInfo ( ) {
+                for symbol in [ "S5028C2012D", "SET5028P2103A", "2S13C2106AB", "HSI06C2101B" ] {
+                    let dw_info_ref = DWInfoRef::parse ( symbol ).unwrap ( );
+                    // borrowed from the symbol
+                    assert_eq ! ( dw_info_ref.series.as_ptr ( ), symbol [ symbol.len ( ) - dw_info_ref.series.len ( ).. ].as_ptr ( ) );
+                    let dw_info = DWInfo::from_str ( symbol ).unwrap ( );
+                    assert_eq ! ( DWInfoRef::from ( &dw_info ), dw_info_ref );
+                    assert_eq ! ( dw_info_ref.expiry ( ), dw_info.expiry ( ) );
+                    assert_eq ! ( DWInfo::from ( dw_info_ref ), dw_info );
+                }
+                assert_eq ! ( DWInfoRef::parse ( "S5028X2012D" ).map ( DWInfo::from ), DWInfo::from_str ( "S5028X2012D" ) );
+            }
         } // tests
     } // mod: dw
 
@@ -780,12 +1173,15 @@ pub mod instrument {
 mod tests {
     use super::*;
     use rand::Rng;
-    use chrono::NaiveDate;
+    use chrono::{
+        Local,
+        NaiveDate,
+    };
 
     #[test]
     /// Test: get current date time
     fn test_get_latest_working_date_time ( ) {
-        let now = Local::now ( ).naive_local ( );
+        let now = clock::now_bangkok ( ).naive_local ( );
 
         let result = get_latest_working_date_time();
         
@@ -0,0 +1,292 @@
+//! # Fixed-point price
+//!
+//! Prices are parsed straight from the source strings into a value and a scale, e.g. "17450.00"
+//! is 1745000 with scale 2, so no precision is lost as in f32. Conversion to float is explicit,
+//! by [Price::to_f64] for analytics.
+
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::{
+        Hash,
+        Hasher,
+    },
+    str::FromStr,
+};
+
+use serde::{
+    Deserialize,
+    Deserializer,
+};
+
+use crate::instrument::dw::Error;
+
+/// Maximum scale of the price, to keep the rescaled value in i64.
+pub const MAX_PRICE_SCALE: u8 = 9;
+
+/// Fixed-point decimal price, as `value / 10^scale`.
+///
+/// Prices of the same amount are equal, and hashed the same, regardless of the scale, e.g.
+/// "0.50" == "0.5".
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Price {
+    value: i64,
+    scale: u8,
+}
+
+impl Price {
+    pub const ZERO: Price = Price { value: 0, scale: 0 };
+
+    /// Returns the price of `value / 10^scale`.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - Unscaled value, e.g. 1745000 for 17450.00
+    /// * `scale` - Number of digits after the decimal point. Up to [MAX_PRICE_SCALE].
+    pub fn new ( value: i64, scale: u8 ) -> Self {
+        assert ! ( scale <= MAX_PRICE_SCALE, "Price scale must be up to {}: {}", MAX_PRICE_SCALE, scale );
+        Price {
+            value,
+            scale,
+        }
+    }
+
+    /// Returns the price from the i32-formatted price, e.g. from [crate::instrument::to_int_price].
+    ///
+    /// # Arguments
+    ///
+    /// * `price` - Price in i32 format
+    /// * `price_digit` - 10 power digits of the i32 format
+    pub fn from_int_price ( price: i32, price_digit: usize ) -> Self {
+        Price::new ( price as i64, price_digit as u8 )
+    }
+
+    /// Returns the unscaled value.
+    pub fn value ( &self ) -> i64 {
+        self.value
+    }
+
+    /// Returns the number of digits after the decimal point.
+    pub fn scale ( &self ) -> u8 {
+        self.scale
+    }
+
+    /// Returns true if the price is zero.
+    pub fn is_zero ( &self ) -> bool {
+        self.value == 0
+    }
+
+    /// Returns the price with given scale. The value is rounded half away from zero if the scale is reduced.
+    ///
+    /// # Arguments
+    ///
+    /// * `scale` - Number of digits after the decimal point. Up to [MAX_PRICE_SCALE].
+    pub fn rescale ( &self, scale: u8 ) -> Self {
+        Price::new ( rescale_value ( self.value, self.scale, scale ), scale )
+    }
+
+    /// Returns the i32-formatted price, based on given [price_digit], same as [crate::instrument::to_int_price]
+    /// but without the error of f32.
+    ///
+    /// # Arguments
+    ///
+    /// * `price_digit` - 10 power digits of the i32 format
+    pub fn to_int_price ( &self, price_digit: usize ) -> i32 {
+        self.rescale ( price_digit as u8 ).value as i32
+    }
+
+    /// Returns the price in f64, for analytics.
+    pub fn to_f64 ( &self ) -> f64 {
+        self.value as f64 / 10f64.powi ( self.scale as i32 )
+    }
+
+    /// Returns the price in f32, for the map of underlying price to DW prices.
+    pub fn to_f32 ( &self ) -> f32 {
+        self.to_f64 ( ) as f32
+    }
+
+    /// Returns the value without the trailing zeros after the decimal point, and its scale.
+    fn normalized ( &self ) -> ( i64, u8 ) {
+        let ( mut value, mut scale ) = ( self.value, self.scale );
+        while scale > 0 && value % 10 == 0 {
+            value /= 10;
+            scale -= 1;
+        }
+        ( value, scale )
+    }
+}
+
+/// Returns the value rescaled from [from] to [to] digits, rounded half away from zero.
+fn rescale_value ( value: i64, from: u8, to: u8 ) -> i64 {
+    match from.cmp ( &to ) {
+        Ordering::Equal => value,
+        Ordering::Less => value * 10i64.pow ( ( to - from ) as u32 ),
+        Ordering::Greater => {
+            let divisor = 10i64.pow ( ( from - to ) as u32 );
+            let quotient = value / divisor;
+            let remainder = value % divisor;
+            if remainder.abs ( ) * 2 >= divisor {
+                quotient + value.signum ( )
+            } else {
+                quotient
+            }
+        },
+    }
+}
+
+impl PartialEq for Price {
+    fn eq ( &self, other: &Self ) -> bool {
+        self.cmp ( other ) == Ordering::Equal
+    }
+}
+
+impl Eq for Price { }
+
+impl Hash for Price {
+    fn hash<H: Hasher> ( &self, state: &mut H ) {
+        self.normalized ( ).hash ( state );
+    }
+}
+
+impl PartialOrd for Price {
+    fn partial_cmp ( &self, other: &Self ) -> Option<Ordering> {
+        Some ( self.cmp ( other ) )
+    }
+}
+
+impl Ord for Price {
+    fn cmp ( &self, other: &Self ) -> Ordering {
+        let scale = self.scale.max ( other.scale );
+        ( self.value as i128 * 10i128.pow ( ( scale - self.scale ) as u32 ) )
+            .cmp ( &( other.value as i128 * 10i128.pow ( ( scale - other.scale ) as u32 ) ) )
+    }
+}
+
+impl FromStr for Price {
+    type Err = Error;
+
+    /// Parses the decimal price, e.g. "17450.00", "-0.5" or "905".
+    fn from_str ( s: &str ) -> Result<Self, Self::Err> {
+        let failed = |info: &str| Error::FailedParsing { symbol: s.into ( ), info: Some ( info.to_owned ( ) ) };
+
+        let trimmed = s.trim ( );
+        let ( negative, digits ) = match trimmed.strip_prefix ( '-' ) {
+            Some ( digits ) => ( true, digits ),
+            None => ( false, trimmed.strip_prefix ( '+' ).unwrap_or ( trimmed ) ),
+        };
+        let ( int_part, frac_part ) = match digits.split_once ( '.' ) {
+            Some ( ( int_part, frac_part ) ) => ( int_part, frac_part ),
+            None => ( digits, "" ),
+        };
+
+        if int_part.is_empty ( ) && frac_part.is_empty ( ) {
+            return Err ( failed ( "Empty price" ) );
+        }
+        if ! int_part.chars ( ).chain ( frac_part.chars ( ) ).all ( |c| c.is_ascii_digit ( ) ) {
+            return Err ( failed ( "Invalid digit in price" ) );
+        }
+        if frac_part.len ( ) > MAX_PRICE_SCALE as usize {
+            return Err ( failed ( "Too many digits after the decimal point" ) );
+        }
+
+        let value = format ! ( "{}{}", int_part, frac_part )
+            .parse::<i64> ( )
+            .map_err ( |_| failed ( "Price is out of range" ) )?;
+
+        Ok ( Price::new ( if negative { -value } else { value }, frac_part.len ( ) as u8 ) )
+    }
+}
+
+impl fmt::Display for Price {
+    fn fmt ( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result {
+        if self.scale == 0 {
+            return write ! ( f, "{}", self.value );
+        }
+        let divisor = 10u64.pow ( self.scale as u32 );
+        let abs = self.value.unsigned_abs ( );
+        write ! (
+            f,
+            "{sign}{int}.{frac:0width$}",
+            sign = if self.value < 0 { "-" } else { "" },
+            int = abs / divisor,
+            frac = abs % divisor,
+            width = self.scale as usize,
+        )
+    }
+}
+
+impl From<Price> for f64 {
+    fn from ( price: Price ) -> f64 {
+        price.to_f64 ( )
+    }
+}
+
+impl<'de> Deserialize<'de> for Price {
+    /// Deserialize the price from json string or number, without going through f32.
+    fn deserialize<D> ( de: D ) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match serde_json::Value::deserialize ( de )? {
+            serde_json::Value::String ( s ) => s.parse ( ).map_err ( serde::de::Error::custom ),
+            serde_json::Value::Number ( n ) => n.to_string ( ).parse ( ).map_err ( serde::de::Error::custom ),
+            v => Err ( serde::de::Error::custom ( format ! ( "Expected price string or number: {}", v ) ) ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_parse_price ( ) {
+        assert_eq ! ( "17450.00".parse::<Price> ( ), Ok ( Price::new ( 1745000, 2 ) ) );
+        assert_eq ! ( "17450.00".parse::<Price> ( ).unwrap ( ).scale ( ), 2 );
+        assert_eq ! ( "905".parse::<Price> ( ), Ok ( Price::new ( 905, 0 ) ) );
+        assert_eq ! ( "-0.5".parse::<Price> ( ), Ok ( Price::new ( -5, 1 ) ) );
+        assert_eq ! ( ".55".parse::<Price> ( ), Ok ( Price::new ( 55, 2 ) ) );
+        assert ! ( "".parse::<Price> ( ).is_err ( ) );
+        assert ! ( "-".parse::<Price> ( ).is_err ( ) );
+        assert ! ( "1.2.3".parse::<Price> ( ).is_err ( ) );
+        assert ! ( "1e3".parse::<Price> ( ).is_err ( ) );
+    }
+
+    #[test]
+    fn test_price_eq_hash_ord ( ) {
+        let a = Price::new ( 50, 2 );
+        let b = Price::new ( 5, 1 );
+        assert_eq ! ( a, b );
+        assert_eq ! ( [ a, b ].iter ( ).collect::<HashSet<_>> ( ).len ( ), 1 );
+        assert ! ( Price::new ( 1745000, 2 ) < Price::new ( 174525, 1 ) );
+        assert ! ( Price::new ( -1, 0 ) < Price::ZERO );
+    }
+
+    #[test]
+    fn test_price_conversion ( ) {
+        let price = "1.2345678".parse::<Price> ( ).unwrap ( );
+        assert_eq ! ( price.to_int_price ( 0 ), 1 );
+        assert_eq ! ( price.to_int_price ( 2 ), 123 );
+        assert_eq ! ( price.to_int_price ( 4 ), 12346 );
+        assert_eq ! ( price.to_int_price ( 9 ), 1234567800 );
+        assert_eq ! ( "-0.005".parse::<Price> ( ).unwrap ( ).to_int_price ( 2 ), -1 );
+        assert_eq ! ( Price::from_int_price ( 90510, 2 ).to_f64 ( ), 905.1 );
+        assert_eq ! ( f64::from ( Price::new ( 1745000, 2 ) ), 17450.0 );
+    }
+
+    #[test]
+    fn test_display_price ( ) {
+        assert_eq ! ( Price::new ( 1745000, 2 ).to_string ( ), "17450.00" );
+        assert_eq ! ( Price::new ( -5, 3 ).to_string ( ), "-0.005" );
+        assert_eq ! ( Price::new ( 905, 0 ).to_string ( ), "905" );
+    }
+
+    #[test]
+    fn test_deserialize_price ( ) {
+        assert_eq ! ( serde_json::from_str::<Price> ( "\"0.55\"" ).unwrap ( ), Price::new ( 55, 2 ) );
+        assert_eq ! ( serde_json::from_str::<Price> ( "17450.0" ).unwrap ( ), Price::new ( 1745000, 2 ) );
+        assert_eq ! ( serde_json::from_str::<Option<Price>> ( "null" ).unwrap ( ), None );
+        assert ! ( serde_json::from_str::<Price> ( "true" ).is_err ( ) );
+    }
+}
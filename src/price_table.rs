@@ -0,0 +1,330 @@
+//! # Structured DW price table
+//!
+//! Unlike the map of underlying price to DW prices, the price table keeps the date of each
+//! column, and both bid and ask sides when the issuer publishes them.
+
+use chrono::{
+    NaiveDate,
+    NaiveDateTime,
+};
+
+use crate::{
+    price::Price,
+    underlying::Currency,
+};
+
+/// Quote of the issuer at one underlying price.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Quote {
+    pub underlying_bid: Price,
+    pub underlying_ask: Option<Price>,
+    pub bid: Price,
+    pub ask: Option<Price>,
+}
+
+/// Matching of an underlying price to the rows of a column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Match {
+    /// The row at the underlying price.
+    Exact,
+    /// The row at the highest underlying price at or below the price.
+    Floor,
+    /// The row at the lowest underlying price at or above the price.
+    Ceil,
+}
+
+/// Quotes of the issuer on one date, sorted by the underlying bid.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceColumn {
+    pub date: NaiveDate,
+    pub quotes: Vec<Quote>,
+}
+
+impl PriceColumn {
+    /// Returns the column of given quotes, sorted by the underlying bid.
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - Date of the quotes.
+    /// * `quotes` - Quotes on the date, in any order.
+    pub fn new ( date: NaiveDate, mut quotes: Vec<Quote> ) -> Self {
+        quotes.sort_by_key ( |q| q.underlying_bid );
+        PriceColumn {
+            date,
+            quotes,
+        }
+    }
+
+    /// Returns the quote matched to given underlying price.
+    ///
+    /// # Arguments
+    ///
+    /// * `underlying` - Underlying bid.
+    /// * `matching` - How to match the underlying price to the rows.
+    pub fn get_quote ( &self, underlying: Price, matching: Match ) -> Option<&Quote> {
+        match ( self.quotes.binary_search_by_key ( &underlying, |q| q.underlying_bid ), matching ) {
+            ( Ok ( idx ), _ ) => self.quotes.get ( idx ),
+            ( Err ( _ ), Match::Exact ) => None,
+            ( Err ( idx ), Match::Floor ) => idx.checked_sub ( 1 ).and_then ( |idx| self.quotes.get ( idx ) ),
+            ( Err ( idx ), Match::Ceil ) => self.quotes.get ( idx ),
+        }
+    }
+
+    /// Returns the DW bid at given underlying price, linearly interpolated between the adjacent rows.
+    ///
+    /// Returns None if the underlying price is out of the rows.
+    ///
+    /// # Arguments
+    ///
+    /// * `underlying` - Underlying bid.
+    pub fn interpolate_bid ( &self, underlying: Price ) -> Option<f64> {
+        let lower = self.get_quote ( underlying, Match::Floor )?;
+        let upper = self.get_quote ( underlying, Match::Ceil )?;
+        if lower.underlying_bid == upper.underlying_bid {
+            return Some ( lower.bid.to_f64 ( ) );
+        }
+        let ratio = ( underlying.to_f64 ( ) - lower.underlying_bid.to_f64 ( ) )
+            / ( upper.underlying_bid.to_f64 ( ) - lower.underlying_bid.to_f64 ( ) );
+        Some ( lower.bid.to_f64 ( ) + ratio * ( upper.bid.to_f64 ( ) - lower.bid.to_f64 ( ) ) )
+    }
+
+    /// Returns the underlying bid where the DW bid reaches given price, or None if it is never reached.
+    ///
+    /// For a table rising with the underlying, e.g. a call, it is the lowest underlying price with
+    /// the DW bid at or above given price. For a falling table, e.g. a put, it is the highest one.
+    ///
+    /// # Arguments
+    ///
+    /// * `bid` - DW bid to be reached.
+    pub fn find_underlying ( &self, bid: Price ) -> Option<Price> {
+        let first = self.quotes.first ( )?;
+        let last = self.quotes.last ( )?;
+        let mut reached = self.quotes.iter ( )
+            .filter ( |q| q.bid >= bid );
+        if first.bid <= last.bid {
+            reached.next ( )
+        } else {
+            reached.next_back ( )
+        }.map ( |q| q.underlying_bid )
+    }
+}
+
+/// DW price table, with one column per date, sorted by date.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceTable {
+    pub dw_symbol: Box<str>,
+    pub underlying_display: Option<Box<str>>,
+    /// Currency of the underlying prices. DW prices are in THB.
+    pub underlying_currency: Currency,
+    /// THB per one unit of [PriceTable::underlying_currency], as used by the issuer, if published.
+    pub fx_rate: Option<f64>,
+    pub publish_time: Option<NaiveDateTime>,
+    pub columns: Vec<PriceColumn>,
+}
+
+impl PriceTable {
+    /// Returns the table of given columns, sorted by date, with the underlying prices in THB.
+    ///
+    /// # Arguments
+    ///
+    /// * `dw_symbol` - DW symbol.
+    /// * `publish_time` - Time of the quotes published by the issuer, if known.
+    /// * `columns` - Columns of the table, in any order.
+    pub fn new ( dw_symbol: Box<str>, publish_time: Option<NaiveDateTime>, mut columns: Vec<PriceColumn> ) -> Self {
+        columns.sort_by_key ( |c| c.date );
+        PriceTable {
+            dw_symbol,
+            underlying_display: None,
+            underlying_currency: Currency::Thb,
+            fx_rate: None,
+            publish_time,
+            columns,
+        }
+    }
+
+    /// Merges the quotes of other table into this table, e.g. tables of the same DW requested
+    /// at different underlying prices.
+    ///
+    /// Quotes at an underlying bid already in the column of the same date are kept from this table.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Table of the same DW.
+    pub fn merge ( &mut self, other: PriceTable ) {
+        if self.underlying_display.is_none ( ) {
+            self.underlying_display = other.underlying_display;
+        }
+        if self.fx_rate.is_none ( ) {
+            self.fx_rate = other.fx_rate;
+        }
+        self.publish_time = self.publish_time.max ( other.publish_time );
+
+        for column in other.columns.into_iter ( ) {
+            match self.columns.iter_mut ( ).find ( |c| c.date == column.date ) {
+                Some ( existing ) => {
+                    for quote in column.quotes.into_iter ( ) {
+                        if existing.quotes.iter ( ).all ( |q| q.underlying_bid != quote.underlying_bid ) {
+                            existing.quotes.push ( quote );
+                        }
+                    }
+                    existing.quotes.sort_by_key ( |q| q.underlying_bid );
+                },
+                None => self.columns.push ( column ),
+            }
+        }
+        self.columns.sort_by_key ( |c| c.date );
+    }
+
+    /// Returns given underlying price in THB, or None if the FX rate of a foreign underlying is
+    /// not known.
+    ///
+    /// # Arguments
+    ///
+    /// * `underlying` - Underlying price in [PriceTable::underlying_currency].
+    pub fn to_thb ( &self, underlying: Price ) -> Option<f64> {
+        match self.underlying_currency {
+            Currency::Thb => Some ( underlying.to_f64 ( ) ),
+            _ => self.fx_rate.map ( |rate| underlying.to_f64 ( ) * rate ),
+        }
+    }
+
+    /// Returns the dates of all columns.
+    pub fn dates ( &self ) -> Vec<NaiveDate> {
+        self.columns.iter ( )
+            .map ( |c| c.date )
+            .collect ( )
+    }
+
+    /// Returns the column of given date.
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - Date of the column.
+    pub fn get_column ( &self, date: NaiveDate ) -> Option<&PriceColumn> {
+        self.columns.iter ( )
+            .find ( |c| c.date == date )
+    }
+
+    /// Returns the quote on given date matched to given underlying price. See [PriceColumn::get_quote].
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - Date of the column.
+    /// * `underlying` - Underlying bid.
+    /// * `matching` - How to match the underlying price to the rows.
+    pub fn get_quote ( &self, date: NaiveDate, underlying: Price, matching: Match ) -> Option<&Quote> {
+        self.get_column ( date )?.get_quote ( underlying, matching )
+    }
+
+    /// Returns the interpolated DW bid on given date. See [PriceColumn::interpolate_bid].
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - Date of the column.
+    /// * `underlying` - Underlying bid.
+    pub fn interpolate_bid ( &self, date: NaiveDate, underlying: Price ) -> Option<f64> {
+        self.get_column ( date )?.interpolate_bid ( underlying )
+    }
+
+    /// Returns the underlying bid where the DW bid reaches given price on given date. See [PriceColumn::find_underlying].
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - Date of the column.
+    /// * `bid` - DW bid to be reached.
+    pub fn find_underlying ( &self, date: NaiveDate, bid: Price ) -> Option<Price> {
+        self.get_column ( date )?.find_underlying ( bid )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{
+        p,
+        quote,
+    };
+
+    #[test]
+    fn test_new_price_table_sorted ( ) {
+        let d1 = NaiveDate::from_ymd_opt ( 2020, 12, 23 ).unwrap ( );
+        let d2 = NaiveDate::from_ymd_opt ( 2020, 12, 24 ).unwrap ( );
+
+        let table = PriceTable::new (
+            "S5028C2012D".into ( ),
+            None,
+            vec ! [
+                PriceColumn::new ( d2, vec ! [ quote ( "901.00", "0.56", None ), quote ( "900.00", "0.55", None ) ] ),
+                PriceColumn::new ( d1, vec ! [ quote ( "900.00", "0.57", None ) ] ),
+            ]
+        );
+
+        assert_eq ! ( table.dates ( ), vec ! [ d1, d2 ] );
+        assert_eq ! ( table.get_column ( d2 ).map ( |c| c.quotes [ 0 ].underlying_bid ), Some ( Price::new ( 90000, 2 ) ) );
+        assert_eq ! ( table.get_column ( d2 ).map ( |c| c.quotes [ 1 ].bid ), Some ( Price::new ( 56, 2 ) ) );
+        assert ! ( table.get_column ( NaiveDate::from_ymd_opt ( 2020, 12, 25 ).unwrap ( ) ).is_none ( ) );
+    }
+
+    #[test]
+    fn test_merge_price_table ( ) {
+        let d1 = NaiveDate::from_ymd_opt ( 2024, 7, 4 ).unwrap ( );
+        let d2 = NaiveDate::from_ymd_opt ( 2024, 7, 5 ).unwrap ( );
+
+        let mut table = PriceTable::new (
+            "DW06C2408F".into ( ),
+            None,
+            vec ! [ PriceColumn::new ( d1, vec ! [ quote ( "17450.00", "0.02", None ), quote ( "17475.00", "0.03", None ) ] ) ]
+        );
+        let mut other = PriceTable::new (
+            "DW06C2408F".into ( ),
+            None,
+            vec ! [
+                PriceColumn::new ( d2, vec ! [ quote ( "18000.00", "0.10", None ) ] ),
+                PriceColumn::new ( d1, vec ! [ quote ( "17475.00", "0.04", None ), quote ( "18000.00", "0.11", None ) ] ),
+            ]
+        );
+        other.underlying_display = Some ( "HSI".into ( ) );
+
+        table.merge ( other );
+
+        assert_eq ! ( table.dates ( ), vec ! [ d1, d2 ] );
+        assert_eq ! ( table.underlying_display.as_deref ( ), Some ( "HSI" ) );
+        assert_eq ! ( table.columns [ 0 ].quotes, vec ! [ quote ( "17450.00", "0.02", None ), quote ( "17475.00", "0.03", None ), quote ( "18000.00", "0.11", None ) ] );
+        assert_eq ! ( table.columns [ 1 ].quotes, vec ! [ quote ( "18000.00", "0.10", None ) ] );
+    }
+
+    #[test]
+    fn test_lookup_call ( ) {
+        let d1 = NaiveDate::from_ymd_opt ( 2020, 12, 23 ).unwrap ( );
+        let table = PriceTable::new (
+            "S5028C2012D".into ( ),
+            None,
+            vec ! [ PriceColumn::new ( d1, vec ! [ quote ( "905.00", "0.55", None ), quote ( "904.60", "0.54", None ), quote ( "905.50", "0.56", None ) ] ) ]
+        );
+
+        assert_eq ! ( table.get_quote ( d1, p ( "905.00" ), Match::Exact ).map ( |q| q.bid ), Some ( p ( "0.55" ) ) );
+        assert_eq ! ( table.get_quote ( d1, p ( "905.3" ), Match::Exact ), None );
+        assert_eq ! ( table.get_quote ( d1, p ( "905.3" ), Match::Floor ).map ( |q| q.bid ), Some ( p ( "0.55" ) ) );
+        assert_eq ! ( table.get_quote ( d1, p ( "905.3" ), Match::Ceil ).map ( |q| q.bid ), Some ( p ( "0.56" ) ) );
+        assert_eq ! ( table.get_quote ( d1, p ( "904" ), Match::Floor ), None );
+        assert_eq ! ( table.get_quote ( d1, p ( "906" ), Match::Ceil ), None );
+
+        let bid = table.interpolate_bid ( d1, p ( "905.3" ) ).unwrap ( );
+        assert ! ( ( bid - 0.556 ).abs ( ) < 1e-9, "{}", bid );
+        assert_eq ! ( table.interpolate_bid ( d1, p ( "904.6" ) ), Some ( 0.54 ) );
+        assert_eq ! ( table.interpolate_bid ( d1, p ( "906" ) ), None );
+
+        assert_eq ! ( table.find_underlying ( d1, p ( "0.55" ) ), Some ( p ( "905" ) ) );
+        assert_eq ! ( table.find_underlying ( d1, p ( "0.545" ) ), Some ( p ( "905" ) ) );
+        assert_eq ! ( table.find_underlying ( d1, p ( "0.60" ) ), None );
+    }
+
+    #[test]
+    fn test_find_underlying_put ( ) {
+        let d1 = NaiveDate::from_ymd_opt ( 2020, 12, 23 ).unwrap ( );
+        let column = PriceColumn::new ( d1, vec ! [ quote ( "905.00", "0.55", None ), quote ( "904.60", "0.56", None ), quote ( "905.50", "0.54", None ) ] );
+
+        assert_eq ! ( column.find_underlying ( p ( "0.55" ) ), Some ( p ( "905" ) ) );
+        assert_eq ! ( column.find_underlying ( p ( "0.54" ) ), Some ( p ( "905.5" ) ) );
+    }
+}
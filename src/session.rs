@@ -0,0 +1,318 @@
+//! # Market sessions
+//!
+//! Trading phases of SET and TFEX, and of the futures that the tables of foreign DWs are quoted on
+//! (HSI futures on HKFE, E-mini S&P 500 futures on CME Globex), so pollers can fetch the tables
+//! only when the issuers are quoting. The session times are in the local time of each market, and
+//! the results in Asia/Bangkok.
+//!
+//! The issuers quote DWs only while SET is trading, so a poller of a foreign DW should fetch when
+//! both the [Market::Set] session and the session of its underlying are trading.
+
+use std::sync::Arc;
+
+use chrono::{
+    DateTime,
+    Datelike,
+    Duration,
+    FixedOffset,
+    NaiveDate,
+    NaiveTime,
+    TimeZone,
+    Utc,
+    Weekday,
+};
+
+use crate::{
+    calendar::TradingCalendar,
+    clock::{
+        bangkok,
+        now_bangkok,
+    },
+    underlying::Underlying,
+};
+
+/// Phases of a trading day, as (start, end, phase) with (hour, minute) in the local time of the
+/// market, from the midnight of the trading day. Hours before 0 or after 24 are on the previous or
+/// the next calendar day, for the sessions across midnight.
+type Schedule = &'static [( ( i32, i32 ), ( i32, i32 ), Phase )];
+
+/// Market of an underlying.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Market {
+    /// The Stock Exchange of Thailand.
+    Set,
+    /// Thailand Futures Exchange, e.g. SET50 index futures.
+    Tfex,
+    /// Hong Kong Futures Exchange, of the HSI futures (HSIc1) with the after-hours session.
+    Hsi,
+    /// CME Globex, of the E-mini S&P 500 futures (ESc1) nearly round the clock.
+    Spx,
+}
+
+impl Market {
+    /// Returns the market of given underlying symbol, in any spelling of [Underlying::resolve],
+    /// from [Underlying::market]. Thai equities are on [Market::Set].
+    ///
+    /// # Arguments
+    ///
+    /// * `underlying_symbol` - Underlying symbol of a DW, e.g. "S50", "SET50", "HSI" or "ADVANC".
+    pub fn for_underlying ( underlying_symbol: &str ) -> Market {
+        Underlying::resolve ( underlying_symbol ).market ( )
+    }
+
+    /// Returns the phases of a trading day.
+    fn schedule ( &self ) -> Schedule {
+        match self {
+            Market::Set => &[
+                ( ( 9, 30 ), ( 10, 0 ), Phase::PreOpen ),
+                ( ( 10, 0 ), ( 12, 30 ), Phase::Morning ),
+                ( ( 12, 30 ), ( 14, 0 ), Phase::Break ),
+                ( ( 14, 0 ), ( 14, 30 ), Phase::PreOpen ),
+                ( ( 14, 30 ), ( 16, 30 ), Phase::Afternoon ),
+                ( ( 16, 30 ), ( 16, 40 ), Phase::PreClose ),
+            ],
+            Market::Tfex => &[
+                ( ( 9, 15 ), ( 9, 45 ), Phase::PreOpen ),
+                ( ( 9, 45 ), ( 12, 30 ), Phase::Morning ),
+                ( ( 12, 30 ), ( 14, 0 ), Phase::Break ),
+                ( ( 14, 0 ), ( 14, 15 ), Phase::PreOpen ),
+                ( ( 14, 15 ), ( 16, 55 ), Phase::Afternoon ),
+            ],
+            // after-hours session until 03:00 of the next day
+            Market::Hsi => &[
+                ( ( 8, 45 ), ( 9, 15 ), Phase::PreOpen ),
+                ( ( 9, 15 ), ( 12, 0 ), Phase::Morning ),
+                ( ( 12, 0 ), ( 13, 0 ), Phase::Break ),
+                ( ( 13, 0 ), ( 16, 30 ), Phase::Afternoon ),
+                ( ( 17, 15 ), ( 27, 0 ), Phase::Night ),
+            ],
+            // no lunch break, from 18:00 ET of the previous day
+            Market::Spx => &[
+                ( ( -6, 0 ), ( 17, 0 ), Phase::Morning ),
+            ],
+        }
+    }
+
+    /// Returns the UTC offset of the market on given local date.
+    fn offset ( &self, date: NaiveDate ) -> FixedOffset {
+        let hours = match self {
+            Market::Set | Market::Tfex => return bangkok ( ),
+            Market::Hsi => 8,
+            Market::Spx if is_us_daylight_saving ( date ) => -4,
+            Market::Spx => -5,
+        };
+        FixedOffset::east_opt ( hours * 3600 ).expect ( "Invalid offset of the market" )
+    }
+}
+
+/// Returns true if given date is in the US daylight saving time, from the second Sunday of March
+/// to the first Sunday of November.
+fn is_us_daylight_saving ( date: NaiveDate ) -> bool {
+    let nth_sunday = |month: u32, n: u8| NaiveDate::from_weekday_of_month_opt ( date.year ( ), month, Weekday::Sun, n )
+        .expect ( "Invalid Sunday of the month" );
+    nth_sunday ( 3, 2 ) <= date && date < nth_sunday ( 11, 1 )
+}
+
+/// Trading phase of a market.
+///
+/// Markets without a lunch break are in [Phase::Morning] for the whole day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Phase {
+    PreOpen,
+    Morning,
+    Break,
+    Afternoon,
+    PreClose,
+    /// After-hours session in the evening, e.g. of the HSI futures.
+    Night,
+    Closed,
+}
+
+impl Phase {
+    /// Returns true if the market is trading continuously.
+    pub fn is_trading ( &self ) -> bool {
+        matches ! ( self, Phase::Morning | Phase::Afternoon | Phase::Night )
+    }
+}
+
+/// Sessions of a market on its trading days.
+#[derive(Debug, Clone)]
+pub struct MarketSession {
+    pub market: Market,
+    calendar: Arc<TradingCalendar>,
+}
+
+impl MarketSession {
+    /// Returns the sessions of given market.
+    ///
+    /// SET and TFEX follow the SET calendar. HSI and SPX skip the weekends only, unless given
+    /// their calendars with [MarketSession::with_calendar]. The session of a trading day may start
+    /// on the previous calendar day, e.g. SPX from 18:00 ET of Sun for Mon.
+    ///
+    /// # Arguments
+    ///
+    /// * `market` - Market.
+    pub fn new ( market: Market ) -> Self {
+        let calendar = match market {
+            Market::Set | Market::Tfex => TradingCalendar::set ( ),
+            Market::Hsi | Market::Spx => Arc::new ( TradingCalendar::default ( ) ),
+        };
+        MarketSession {
+            market,
+            calendar,
+        }
+    }
+
+    /// Returns the sessions of given market on the trading days of given calendar.
+    ///
+    /// # Arguments
+    ///
+    /// * `market` - Market.
+    /// * `calendar` - Calendar of the market, in its local dates.
+    pub fn with_calendar ( market: Market, calendar: TradingCalendar ) -> Self {
+        MarketSession {
+            market,
+            calendar: Arc::new ( calendar ),
+        }
+    }
+
+    /// Returns the phases of given local date, as (start, end, phase) in UTC. Empty if not a trading day.
+    fn phases_of ( &self, date: NaiveDate ) -> Vec<( DateTime<Utc>, DateTime<Utc>, Phase )> {
+        if ! self.calendar.is_trading_day ( date ) {
+            return Vec::new ( );
+        }
+        let offset = self.market.offset ( date );
+        let to_utc = |( hour, minute ): ( i32, i32 )| offset
+            .from_local_datetime ( &( date.and_time ( NaiveTime::MIN ) + Duration::minutes ( ( hour * 60 + minute ) as i64 ) ) )
+            .single ( )
+            .expect ( "Invalid session date/time" )
+            .with_timezone ( &Utc );
+        self.market.schedule ( ).iter ( )
+            .map ( |&( start, end, phase )| ( to_utc ( start ), to_utc ( end ), phase ) )
+            .collect ( )
+    }
+
+    /// Returns the local date of the market at given time.
+    fn local_date ( &self, time: DateTime<Utc> ) -> NaiveDate {
+        let date = time.with_timezone ( &bangkok ( ) ).date_naive ( );
+        time.with_timezone ( &self.market.offset ( date ) ).date_naive ( )
+    }
+
+    /// Returns the phase at given time.
+    ///
+    /// # Arguments
+    ///
+    /// * `time` - Time in any time zone.
+    pub fn phase_at<Tz: TimeZone> ( &self, time: &DateTime<Tz> ) -> Phase {
+        let time = time.with_timezone ( &Utc );
+        let date = self.local_date ( time );
+        [ date - Duration::days ( 1 ), date, date + Duration::days ( 1 ) ]
+            .iter ( )
+            .flat_map ( |date| self.phases_of ( *date ) )
+            .find ( |( start, end, _ )| *start <= time && time < *end )
+            .map ( |( _, _, phase )| phase )
+            .unwrap_or ( Phase::Closed )
+    }
+
+    /// Returns the time of the next change of the phase after given time, in Asia/Bangkok, and the
+    /// phase since then. None if the market does not trade within a month.
+    ///
+    /// # Arguments
+    ///
+    /// * `time` - Time in any time zone.
+    pub fn next_transition<Tz: TimeZone> ( &self, time: &DateTime<Tz> ) -> Option<( DateTime<FixedOffset>, Phase )> {
+        let time = time.with_timezone ( &Utc );
+        let current = self.phase_at ( &time );
+        let date = self.local_date ( time );
+        let mut boundaries = ( -1..=31 )
+            .flat_map ( |days| self.phases_of ( date + Duration::days ( days ) ) )
+            .flat_map ( |( start, end, _ )| [ start, end ] )
+            .filter ( |boundary| *boundary > time )
+            .collect::<Vec<DateTime<Utc>>> ( );
+        boundaries.sort ( );
+        boundaries.into_iter ( )
+            .map ( |boundary| ( boundary, self.phase_at ( &boundary ) ) )
+            .find ( |( _, phase )| *phase != current )
+            .map ( |( boundary, phase )| ( boundary.with_timezone ( &bangkok ( ) ), phase ) )
+    }
+
+    /// Returns the current phase, from [crate::clock::now_bangkok].
+    pub fn current_phase ( &self ) -> Phase {
+        self.phase_at ( &now_bangkok ( ) )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::BANGKOK_UTC_OFFSET;
+
+    fn bangkok_time ( month: u32, day: u32, hour: u32, minute: u32 ) -> DateTime<FixedOffset> {
+        FixedOffset::east_opt ( BANGKOK_UTC_OFFSET ).unwrap ( )
+            .with_ymd_and_hms ( 2020, month, day, hour, minute, 0 )
+            .unwrap ( )
+    }
+
+    #[test]
+    fn test_for_underlying ( ) {
+        assert_eq ! ( Market::for_underlying ( "S50" ), Market::Tfex );
+        assert_eq ! ( Market::for_underlying ( "HSI" ), Market::Hsi );
+        assert_eq ! ( Market::for_underlying ( "SPX" ), Market::Spx );
+        assert_eq ! ( Market::for_underlying ( "ADVANC" ), Market::Set );
+
+        // other spellings of the issuers
+        assert_eq ! ( Market::for_underlying ( "SET50" ), Market::Tfex );
+        assert_eq ! ( Market::for_underlying ( "SET50*" ), Market::Tfex );
+        assert_eq ! ( Market::for_underlying ( "HANG SENG" ), Market::Hsi );
+        assert_eq ! ( Market::for_underlying ( "S&P500" ), Market::Spx );
+        let dw_info = "SET5028P2103A".parse::<crate::instrument::dw::DWInfo> ( ).unwrap ( );
+        assert_eq ! ( Market::for_underlying ( &dw_info.underlying_symbol ), Market::Tfex );
+    }
+
+    #[test]
+    fn test_set ( ) {
+        let session = MarketSession::new ( Market::Set );
+        assert_eq ! ( session.phase_at ( &bangkok_time ( 12, 23, 11, 0 ) ), Phase::Morning );
+        assert_eq ! ( session.next_transition ( &bangkok_time ( 12, 23, 11, 0 ) ), Some ( ( bangkok_time ( 12, 23, 12, 30 ), Phase::Break ) ) );
+        assert_eq ! ( session.phase_at ( &bangkok_time ( 12, 23, 14, 10 ) ), Phase::PreOpen );
+        assert_eq ! ( session.phase_at ( &bangkok_time ( 12, 23, 16, 30 ) ), Phase::PreClose );
+
+        // closed over the holiday and the weekend, until the pre-open of Mon
+        assert_eq ! ( session.phase_at ( &bangkok_time ( 12, 31, 11, 0 ) ), Phase::Closed );
+        assert_eq ! ( session.next_transition ( &bangkok_time ( 12, 30, 17, 0 ) ).unwrap ( ).0.date_naive ( ), NaiveDate::from_ymd_opt ( 2021, 1, 4 ).unwrap ( ) );
+    }
+
+    #[test]
+    fn test_foreign ( ) {
+        // 08:45 in Bangkok is 09:45 in Hong Kong
+        let hsi = MarketSession::new ( Market::Hsi );
+        assert_eq ! ( hsi.phase_at ( &bangkok_time ( 12, 23, 8, 45 ) ), Phase::Morning );
+        assert_eq ! ( hsi.next_transition ( &bangkok_time ( 12, 23, 8, 45 ) ), Some ( ( bangkok_time ( 12, 23, 11, 0 ), Phase::Break ) ) );
+
+        // the HSI futures after-hours session: 17:15-03:00 in Hong Kong is 16:15-02:00 in Bangkok
+        assert_eq ! ( hsi.phase_at ( &bangkok_time ( 12, 23, 15, 45 ) ), Phase::Closed );
+        assert_eq ! ( hsi.phase_at ( &bangkok_time ( 12, 24, 1, 0 ) ), Phase::Night );
+        assert ! ( hsi.phase_at ( &bangkok_time ( 12, 24, 1, 0 ) ).is_trading ( ) );
+        assert_eq ! ( hsi.next_transition ( &bangkok_time ( 12, 24, 1, 0 ) ), Some ( ( bangkok_time ( 12, 24, 2, 0 ), Phase::Closed ) ) );
+
+        // US standard time in Dec: the daily halt 17:00-18:00 EST is 05:00-06:00 in Bangkok
+        let spx = MarketSession::new ( Market::Spx );
+        assert_eq ! ( spx.phase_at ( &bangkok_time ( 12, 23, 21, 0 ) ), Phase::Morning );
+        assert_eq ! ( spx.phase_at ( &bangkok_time ( 12, 24, 4, 59 ) ), Phase::Morning );
+        assert_eq ! ( spx.next_transition ( &bangkok_time ( 12, 24, 3, 0 ) ), Some ( ( bangkok_time ( 12, 24, 5, 0 ), Phase::Closed ) ) );
+        assert_eq ! ( spx.next_transition ( &bangkok_time ( 12, 24, 5, 30 ) ), Some ( ( bangkok_time ( 12, 24, 6, 0 ), Phase::Morning ) ) );
+
+        // trading through the SET sessions on weekdays, closed from 05:00 of Sat to 06:00 of Mon
+        let set = MarketSession::new ( Market::Set );
+        for time in [ bangkok_time ( 12, 23, 10, 30 ), bangkok_time ( 12, 23, 15, 0 ) ] {
+            assert ! ( set.phase_at ( &time ).is_trading ( ) && spx.phase_at ( &time ).is_trading ( ) );
+            assert ! ( set.phase_at ( &time ).is_trading ( ) && hsi.phase_at ( &time ).is_trading ( ) );
+        }
+        assert_eq ! ( spx.phase_at ( &bangkok_time ( 12, 26, 10, 0 ) ), Phase::Closed );
+        assert_eq ! ( spx.next_transition ( &bangkok_time ( 12, 26, 10, 0 ) ), Some ( ( bangkok_time ( 12, 28, 6, 0 ), Phase::Morning ) ) );
+
+        // US daylight saving time in Jul: the daily halt is 04:00-05:00 in Bangkok
+        assert_eq ! ( spx.phase_at ( &bangkok_time ( 7, 1, 4, 30 ) ), Phase::Closed );
+        assert ! ( spx.phase_at ( &bangkok_time ( 7, 1, 20, 45 ) ).is_trading ( ) );
+    }
+}
@@ -0,0 +1,114 @@
+//! # Test helpers
+//!
+//! Request counters of the scraper mocks, and builders of prices and price tables shared by the
+//! test modules.
+
+use chrono::NaiveDate;
+
+use crate::{
+    price::Price,
+    price_table::{
+        PriceColumn,
+        PriceTable,
+        Quote,
+    },
+};
+
+/// Declares the per-thread request state of a scraper module, counted by the scraper in tests.
+macro_rules! gen_mock {
+    ($m:ident) => {
+        use std::thread;
+        use std::sync::Mutex;
+        pub struct TestState {
+            pub count: u32,
+            pub last_dw_symbol: String,
+        }
+        lazy_static::lazy_static! {
+            pub static ref TEST_STATE: Mutex<std::collections::HashMap<thread::ThreadId, TestState>> = Mutex::new(std::collections::HashMap::new());
+        }
+    };
+}
+
+/// Returns, or sets with a value, the number of requests of the current thread to a scraper module.
+macro_rules! test_count {
+    ($m:ident) => {
+        crate::$m::TEST_STATE.lock().unwrap().get(&std::thread::current().id()).map(|s| s.count).unwrap_or(0)
+    };
+    ($m:ident, $v:expr) => {
+        if let Some(s) = crate::$m::TEST_STATE.lock().unwrap().get_mut(&std::thread::current().id()) { s.count = $v; }
+    };
+}
+
+/// Returns the DW symbol of the last request of the current thread to a scraper module.
+macro_rules! test_last_dw_symbol {
+    ($m:ident) => {
+        crate::$m::TEST_STATE.lock().unwrap().get(&std::thread::current().id()).map(|s| s.last_dw_symbol.clone()).unwrap_or_default()
+    };
+}
+
+#[allow(unused_imports)]
+pub(crate) use {gen_mock, test_count, test_last_dw_symbol};
+
+/// Returns the price of given decimal string.
+pub(crate) fn p ( s: &str ) -> Price {
+    s.parse ( ).unwrap ( )
+}
+
+/// Returns the date of given day in December 2020, the month of the DW28 fixtures.
+pub(crate) fn date ( day: u32 ) -> NaiveDate {
+    NaiveDate::from_ymd_opt ( 2020, 12, day ).unwrap ( )
+}
+
+/// Returns the quote of given underlying bid, DW bid and DW ask, without the underlying ask.
+pub(crate) fn quote ( underlying_bid: &str, bid: &str, ask: Option<&str> ) -> Quote {
+    Quote {
+        underlying_bid: p ( underlying_bid ),
+        underlying_ask: None,
+        bid: p ( bid ),
+        ask: ask.map ( p ),
+    }
+}
+
+/// Returns the column on given day of rows of (underlying bid, DW bid), without asks.
+pub(crate) fn column ( day: u32, rows: &[(&str, &str)] ) -> PriceColumn {
+    PriceColumn::new (
+        date ( day ),
+        rows.iter ( )
+            .map ( |(underlying, bid)| quote ( underlying, bid, None ) )
+            .collect ( )
+    )
+}
+
+/// Returns the column on given day of rows of (underlying bid, DW bid, DW ask).
+pub(crate) fn column_with_asks ( day: u32, rows: &[(&str, &str, Option<&str>)] ) -> PriceColumn {
+    PriceColumn::new (
+        date ( day ),
+        rows.iter ( )
+            .map ( |(underlying, bid, ask)| quote ( underlying, bid, *ask ) )
+            .collect ( )
+    )
+}
+
+/// Returns the column on given day of rows 0..=[last] at the underlying bid of [from] + [step] * row,
+/// in 0.01, with the (DW bid, DW ask) of each row.
+pub(crate) fn ladder_column<F: Fn ( i64 ) -> ( Price, Option<Price> )> ( day: u32, from: i64, step: i64, last: i64, prices: F ) -> PriceColumn {
+    PriceColumn::new (
+        date ( day ),
+        ( 0..=last )
+            .map ( |row| {
+                let ( bid, ask ) = prices ( row );
+                Quote {
+                    underlying_bid: Price::new ( from + step * row, 2 ),
+                    underlying_ask: None,
+                    bid,
+                    ask,
+                }
+            } )
+            .collect ( )
+    )
+}
+
+/// Returns the table of given DW symbol and columns.
+pub(crate) fn table ( dw_symbol: &str, columns: Vec<PriceColumn> ) -> PriceTable {
+    PriceTable::new ( dw_symbol.into ( ), None, columns )
+}
@@ -0,0 +1,352 @@
+//! # Tick-size ladders
+//!
+//! Each ladder is a list of price bands with their tick size. The scrapers and the analytics share
+//! these ladders to snap, step and count prices, instead of encoding the tick table in place.
+
+use crate::{
+    price::Price,
+    underlying::Underlying,
+};
+
+/// Direction to snap an off-tick price to the ladder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Snap {
+    /// The highest tick at or below the price.
+    Down,
+    /// The lowest tick at or above the price.
+    Up,
+    /// The nearest tick. The upper one if the price is in the middle.
+    Nearest,
+}
+
+/// Tick-size ladder, as price bands of (lower bound, tick size) in the ladder's scale.
+///
+/// The lower bound of each band must be a multiple of its tick size, and the first band starts at 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TickLadder {
+    pub name: &'static str,
+    scale: u8,
+    bands: &'static [(i64, i64)],
+}
+
+/// SET equities.
+///
+/// | Price | Tick |
+/// | ---- | ---- |
+/// | 0.00-1.99 | 0.01 |
+/// | 2.00-4.98 | 0.02 |
+/// | 5.00-9.95 | 0.05 |
+/// | 10.00-24.90 | 0.10 |
+/// | 25.00-99.75 | 0.25 |
+/// | 100.00-199.50 | 0.50 |
+/// | 200.00-399.00 | 1.00 |
+/// | 400.00-upper | 2.00 |
+pub const SET_EQUITY: TickLadder = TickLadder {
+    name: "SET equity",
+    scale: 2,
+    bands: &[ ( 0, 1 ), ( 200, 2 ), ( 500, 5 ), ( 1000, 10 ), ( 2500, 25 ), ( 10000, 50 ), ( 20000, 100 ), ( 40000, 200 ) ],
+};
+
+/// DWs listed on SET, in the same price bands as SET equities.
+pub const DW: TickLadder = TickLadder {
+    name: "DW",
+    ..SET_EQUITY
+};
+
+/// SET50 index level, in 0.01 point.
+pub const SET50_INDEX: TickLadder = TickLadder {
+    name: "SET50 index",
+    scale: 2,
+    bands: &[ ( 0, 1 ) ],
+};
+
+/// SET50 level in 0.50 point, the grid of the DW13 SET50 tables, keyed on the SET50 futures, e.g.
+/// S50Z20 in 2020 and S50M23 in 2023.
+pub const SET50_HALF_POINT: TickLadder = TickLadder {
+    name: "SET50 half point",
+    scale: 2,
+    bands: &[ ( 0, 50 ) ],
+};
+
+/// Hang Seng level, in 1 point, the tick of the HSI futures (HSIc1) that the HSI DW tables are
+/// quoted on.
+pub const HSI: TickLadder = TickLadder {
+    name: "HSI",
+    scale: 0,
+    bands: &[ ( 0, 1 ) ],
+};
+
+/// S&P 500 level, in 0.25 point, the tick of the E-mini S&P 500 futures (ESc1) that the SPX DW
+/// tables are quoted on.
+pub const SPX: TickLadder = TickLadder {
+    name: "SPX",
+    scale: 2,
+    bands: &[ ( 0, 25 ) ],
+};
+
+impl TickLadder {
+    /// Returns the ladder of given underlying symbol, e.g. "S50", "SET50", "HSI", "SPX", from
+    /// [Underlying::tick_ladder].
+    ///
+    /// Symbols not known to [Underlying::resolve] are treated as SET equities.
+    ///
+    /// # Arguments
+    ///
+    /// * `underlying_symbol` - Underlying symbol, as in [crate::instrument::dw::DWInfo].
+    pub fn for_underlying ( underlying_symbol: &str ) -> TickLadder {
+        Underlying::resolve ( underlying_symbol ).tick_ladder
+    }
+
+    /// Returns the tick size for the next higher price.
+    ///
+    /// # Arguments
+    ///
+    /// * `price` - Price in the ladder.
+    pub fn tick_size ( &self, price: Price ) -> Price {
+        self.price_of ( self.band_of ( self.units_of ( price, Snap::Down ) ).1 )
+    }
+
+    /// Returns true if the price is on a tick of the ladder.
+    ///
+    /// # Arguments
+    ///
+    /// * `price` - Price to be checked.
+    pub fn is_on_tick ( &self, price: Price ) -> bool {
+        if price < Price::ZERO {
+            return false;
+        }
+        let units = self.units_of ( price, Snap::Down );
+        self.price_of ( units ) == price && self.snap_units_down ( units ) == units
+    }
+
+    /// Returns the price snapped to the ladder. Negative prices are snapped to 0.
+    ///
+    /// # Arguments
+    ///
+    /// * `price` - Price to be snapped.
+    /// * `snap` - Direction to snap if the price is not on a tick.
+    pub fn snap ( &self, price: Price, snap: Snap ) -> Price {
+        if price <= Price::ZERO {
+            return self.price_of ( 0 );
+        }
+        let down = self.snap_units_down ( self.units_of ( price, Snap::Down ) );
+        let down_price = self.price_of ( down );
+        if down_price == price {
+            return down_price;
+        }
+        let up_price = self.price_of ( down + self.band_of ( down ).1 );
+        match snap {
+            Snap::Down => down_price,
+            Snap::Up => up_price,
+            Snap::Nearest => {
+                let scale = price.scale ( ).max ( self.scale );
+                let ( price, down, up ) = ( price.rescale ( scale ).value ( ), down_price.rescale ( scale ).value ( ), up_price.rescale ( scale ).value ( ) );
+                if price - down < up - price {
+                    down_price
+                } else {
+                    up_price
+                }
+            },
+        }
+    }
+
+    /// Returns the price of [n] ticks from given price, or None if it is below 0.
+    ///
+    /// If the price is not on a tick, the first step is to the adjacent tick in the direction.
+    ///
+    /// # Arguments
+    ///
+    /// * `price` - Price to step from.
+    /// * `n` - Number of ticks. Negative to step down.
+    pub fn step ( &self, price: Price, n: i64 ) -> Option<Price> {
+        let start = if n < 0 { self.snap ( price, Snap::Up ) } else { self.snap ( price, Snap::Down ) };
+        let index = self.index_of ( self.units_of ( start, Snap::Down ) ) + n;
+        if index < 0 {
+            None
+        } else {
+            Some ( self.price_of ( self.units_at ( index ) ) )
+        }
+    }
+
+    /// Returns the number of ticks from [from] to [to]. Negative if [to] is lower.
+    ///
+    /// Off-tick prices are snapped down.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - Price to count from.
+    /// * `to` - Price to count to.
+    pub fn ticks_between ( &self, from: Price, to: Price ) -> i64 {
+        self.index_of ( self.units_of ( self.snap ( to, Snap::Down ), Snap::Down ) )
+            - self.index_of ( self.units_of ( self.snap ( from, Snap::Down ), Snap::Down ) )
+    }
+
+    /// Returns the iterator of all ticks from [from] to [to], inclusive, in ascending order.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - Lowest price. Snapped up if not on a tick.
+    /// * `to` - Highest price. Snapped down if not on a tick.
+    pub fn range ( &self, from: Price, to: Price ) -> TickRange {
+        TickRange {
+            ladder: *self,
+            next: self.units_of ( self.snap ( from, Snap::Up ), Snap::Down ),
+            last: self.units_of ( self.snap ( to, Snap::Down ), Snap::Down ),
+        }
+    }
+
+    /// Returns the band of (lower bound, tick size) of given units.
+    fn band_of ( &self, units: i64 ) -> ( i64, i64 ) {
+        *self.bands.iter ( )
+            .rev ( )
+            .find ( |&&(lower, _)| lower <= units )
+            .unwrap_or ( &self.bands [ 0 ] )
+    }
+
+    /// Returns the highest tick at or below given units.
+    fn snap_units_down ( &self, units: i64 ) -> i64 {
+        let ( lower, tick ) = self.band_of ( units );
+        lower + ( units - lower ).max ( 0 ) / tick * tick
+    }
+
+    /// Returns the number of ticks from 0 to given on-tick units.
+    fn index_of ( &self, units: i64 ) -> i64 {
+        let mut index = 0;
+        for ( idx, &(lower, tick) ) in self.bands.iter ( ).enumerate ( ) {
+            let upper = self.bands.get ( idx + 1 ).map ( |&(upper, _)| upper ).unwrap_or ( i64::MAX );
+            if units < upper {
+                return index + ( units - lower ) / tick;
+            }
+            index += ( upper - lower ) / tick;
+        }
+        index
+    }
+
+    /// Returns the units of the tick at given index from 0.
+    fn units_at ( &self, mut index: i64 ) -> i64 {
+        for ( idx, &(lower, tick) ) in self.bands.iter ( ).enumerate ( ) {
+            let count = self.bands.get ( idx + 1 ).map ( |&(upper, _)| ( upper - lower ) / tick ).unwrap_or ( i64::MAX );
+            if index < count {
+                return lower + index * tick;
+            }
+            index -= count;
+        }
+        unreachable ! ( )
+    }
+
+    /// Returns the price in units of the ladder's scale, rounded by given direction.
+    fn units_of ( &self, price: Price, snap: Snap ) -> i64 {
+        if price.scale ( ) <= self.scale {
+            return price.rescale ( self.scale ).value ( );
+        }
+        let divisor = 10i64.pow ( ( price.scale ( ) - self.scale ) as u32 );
+        let value = price.value ( );
+        match snap {
+            Snap::Down => value.div_euclid ( divisor ),
+            Snap::Up => -( -value ).div_euclid ( divisor ),
+            Snap::Nearest => price.rescale ( self.scale ).value ( ),
+        }
+    }
+
+    /// Returns the price of given units.
+    fn price_of ( &self, units: i64 ) -> Price {
+        Price::new ( units, self.scale )
+    }
+}
+
+/// Iterator of the ticks in a price range. See [TickLadder::range].
+#[derive(Debug, Clone)]
+pub struct TickRange {
+    ladder: TickLadder,
+    next: i64,
+    last: i64,
+}
+
+impl Iterator for TickRange {
+    type Item = Price;
+
+    fn next ( &mut self ) -> Option<Price> {
+        if self.next > self.last {
+            return None;
+        }
+        let price = self.ladder.price_of ( self.next );
+        self.next += self.ladder.band_of ( self.next ).1;
+        Some ( price )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::p;
+
+    #[test]
+    fn test_tick_size ( ) {
+        assert_eq ! ( SET_EQUITY.tick_size ( p ( "1.99" ) ), p ( "0.01" ) );
+        assert_eq ! ( SET_EQUITY.tick_size ( p ( "2" ) ), p ( "0.02" ) );
+        assert_eq ! ( SET_EQUITY.tick_size ( p ( "399" ) ), p ( "1" ) );
+        assert_eq ! ( SET_EQUITY.tick_size ( p ( "400" ) ), p ( "2" ) );
+        assert_eq ! ( HSI.tick_size ( p ( "17450" ) ), p ( "1" ) );
+        assert_eq ! ( SET50_INDEX.tick_size ( p ( "905.3" ) ), p ( "0.01" ) );
+    }
+
+    #[test]
+    fn test_snap ( ) {
+        assert_eq ! ( SET_EQUITY.snap ( p ( "2.03" ), Snap::Down ), p ( "2.02" ) );
+        assert_eq ! ( SET_EQUITY.snap ( p ( "2.03" ), Snap::Up ), p ( "2.04" ) );
+        assert_eq ! ( SET_EQUITY.snap ( p ( "26.30" ), Snap::Nearest ), p ( "26.25" ) );
+        assert_eq ! ( SET_EQUITY.snap ( p ( "26.375" ), Snap::Nearest ), p ( "26.50" ) );
+        assert_eq ! ( SET_EQUITY.snap ( p ( "1.999" ), Snap::Up ), p ( "2.00" ) );
+        assert_eq ! ( SET_EQUITY.snap ( p ( "-1" ), Snap::Up ), Price::ZERO );
+        assert_eq ! ( HSI.snap ( p ( "17450.6" ), Snap::Nearest ), p ( "17451" ) );
+        assert ! ( DW.is_on_tick ( p ( "0.55" ) ) );
+        assert ! ( ! SET_EQUITY.is_on_tick ( p ( "2.01" ) ) );
+        assert ! ( ! SET_EQUITY.is_on_tick ( p ( "0.555" ) ) );
+    }
+
+    #[test]
+    fn test_step ( ) {
+        assert_eq ! ( SET_EQUITY.step ( p ( "2.00" ), -1 ), Some ( p ( "1.99" ) ) );
+        assert_eq ! ( SET_EQUITY.step ( p ( "1.99" ), 1 ), Some ( p ( "2.00" ) ) );
+        assert_eq ! ( SET_EQUITY.step ( p ( "4.98" ), 2 ), Some ( p ( "5.05" ) ) );
+        assert_eq ! ( SET_EQUITY.step ( p ( "400" ), -1 ), Some ( p ( "399" ) ) );
+        assert_eq ! ( SET_EQUITY.step ( p ( "2.03" ), 1 ), Some ( p ( "2.04" ) ) );
+        assert_eq ! ( SET_EQUITY.step ( p ( "2.03" ), -1 ), Some ( p ( "2.02" ) ) );
+        assert_eq ! ( SET_EQUITY.step ( p ( "0.01" ), -2 ), None );
+        assert_eq ! ( SPX.step ( p ( "3700.00" ), 5 ), Some ( p ( "3701.25" ) ) );
+        assert_eq ! ( SPX.ticks_between ( p ( "3700.00" ), p ( "3701.00" ) ), 4 );
+        assert ! ( ! SPX.is_on_tick ( p ( "3700.10" ) ) );
+    }
+
+    #[test]
+    fn test_ticks_between ( ) {
+        assert_eq ! ( SET_EQUITY.ticks_between ( p ( "1.98" ), p ( "2.04" ) ), 4 );
+        assert_eq ! ( SET_EQUITY.ticks_between ( p ( "2.04" ), p ( "1.98" ) ), -4 );
+        assert_eq ! ( SET_EQUITY.ticks_between ( p ( "0" ), p ( "2" ) ), 200 );
+        assert_eq ! ( SET_EQUITY.ticks_between ( p ( "399" ), p ( "404" ) ), 3 );
+        assert_eq ! ( HSI.ticks_between ( p ( "17450" ), p ( "17475" ) ), 25 );
+
+        for ( from, n ) in [ ( "0.5", 300 ), ( "23.5", 77 ), ( "150", 400 ) ] {
+            let to = SET_EQUITY.step ( p ( from ), n ).unwrap ( );
+            assert_eq ! ( SET_EQUITY.ticks_between ( p ( from ), to ), n );
+        }
+    }
+
+    #[test]
+    fn test_range ( ) {
+        assert_eq ! (
+            SET_EQUITY.range ( p ( "1.975" ), p ( "2.05" ) ).collect::<Vec<Price>> ( ),
+            vec ! [ p ( "1.98" ), p ( "1.99" ), p ( "2.00" ), p ( "2.02" ), p ( "2.04" ) ]
+        );
+        assert_eq ! ( SET_EQUITY.range ( p ( "3" ), p ( "2" ) ).count ( ), 0 );
+    }
+
+    #[test]
+    fn test_for_underlying ( ) {
+        assert_eq ! ( TickLadder::for_underlying ( "S50" ), SET50_INDEX );
+        assert_eq ! ( TickLadder::for_underlying ( "SET50" ), SET50_INDEX );
+        assert_eq ! ( TickLadder::for_underlying ( "hsi" ), HSI );
+        assert_eq ! ( TickLadder::for_underlying ( "SPX" ), SPX );
+        assert_eq ! ( TickLadder::for_underlying ( "ADVANC" ), SET_EQUITY );
+    }
+}
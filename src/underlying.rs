@@ -0,0 +1,237 @@
+//! # Underlying symbols
+//!
+//! Issuers spell the same underlying differently, e.g. "S50", "SET50" and "SET50*" for the SET50
+//! index, or "ADVA" in DW symbols for ADVANC. [Underlying::resolve] maps any of them to the
+//! canonical [Underlying], with its SET ticker, RIC, currency, price precision and tick ladder.
+//! The tick ladders and the markets of the underlyings are derived from it, so every issuer
+//! spelling is classified the same.
+
+use std::fmt;
+
+use crate::{
+    session::Market,
+    ticks::{
+        self,
+        TickLadder,
+    },
+};
+
+/// Asset class of an underlying.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AssetClass {
+    SetStock,
+    Set50Index,
+    ForeignIndex,
+    ForeignStock,
+}
+
+/// Currency of underlying prices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Currency {
+    Thb,
+    Hkd,
+    Usd,
+}
+
+impl Currency {
+    /// Returns the ISO 4217 code, e.g. "THB".
+    pub fn code ( &self ) -> &'static str {
+        match self {
+            Currency::Thb => "THB",
+            Currency::Hkd => "HKD",
+            Currency::Usd => "USD",
+        }
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt ( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result {
+        f.write_str ( self.code ( ) )
+    }
+}
+
+/// Row of the mapping table, as (canonical symbol, SET ticker, RIC, asset class, currency, price
+/// digits, tick ladder, DW prefix, other aliases).
+type Mapping = ( &'static str, Option<&'static str>, &'static str, AssetClass, Currency, u8, TickLadder, &'static str, &'static [&'static str] );
+
+/// Known underlyings.
+///
+/// SET stocks are listed only if their DW prefix is not their ticker. See [Underlying::resolve].
+const UNDERLYINGS: &[Mapping] = &[
+    ( "SET50", Some ( "SET50" ), "S50cv1", AssetClass::Set50Index, Currency::Thb, 2, ticks::SET50_INDEX, "S50", &[ "SET50*" ] ),
+    ( "HSI", None, "HSIc1", AssetClass::ForeignIndex, Currency::Hkd, 0, ticks::HSI, "HSI", &[ "HANG SENG" ] ),
+    ( "SPX", None, "ESc1", AssetClass::ForeignIndex, Currency::Usd, 2, ticks::SPX, "SPX", &[ "S&P500", "S&P 500" ] ),
+    // SET stocks with abbreviated DW prefixes
+    ( "ADVANC", Some ( "ADVANC" ), "ADVANC.BK", AssetClass::SetStock, Currency::Thb, 2, ticks::SET_EQUITY, "ADVA", &[ ] ),
+    ( "AEONTS", Some ( "AEONTS" ), "AEONTS.BK", AssetClass::SetStock, Currency::Thb, 2, ticks::SET_EQUITY, "AEON", &[ ] ),
+    ( "AMATA", Some ( "AMATA" ), "AMATA.BK", AssetClass::SetStock, Currency::Thb, 2, ticks::SET_EQUITY, "AMAT", &[ ] ),
+    ( "BANPU", Some ( "BANPU" ), "BANPU.BK", AssetClass::SetStock, Currency::Thb, 2, ticks::SET_EQUITY, "BANP", &[ ] ),
+    ( "CENTEL", Some ( "CENTEL" ), "CENTEL.BK", AssetClass::SetStock, Currency::Thb, 2, ticks::SET_EQUITY, "CENT", &[ ] ),
+    ( "CPALL", Some ( "CPALL" ), "CPALL.BK", AssetClass::SetStock, Currency::Thb, 2, ticks::SET_EQUITY, "CPAL", &[ ] ),
+    ( "DOHOME", Some ( "DOHOME" ), "DOHOME.BK", AssetClass::SetStock, Currency::Thb, 2, ticks::SET_EQUITY, "DOHO", &[ ] ),
+    ( "GUNKUL", Some ( "GUNKUL" ), "GUNKUL.BK", AssetClass::SetStock, Currency::Thb, 2, ticks::SET_EQUITY, "GUNK", &[ ] ),
+    ( "INTUCH", Some ( "INTUCH" ), "INTUCH.BK", AssetClass::SetStock, Currency::Thb, 2, ticks::SET_EQUITY, "INTU", &[ ] ),
+    ( "KBANK", Some ( "KBANK" ), "KBANK.BK", AssetClass::SetStock, Currency::Thb, 2, ticks::SET_EQUITY, "KBAN", &[ ] ),
+    ( "PLANB", Some ( "PLANB" ), "PLANB.BK", AssetClass::SetStock, Currency::Thb, 2, ticks::SET_EQUITY, "PLAN", &[ ] ),
+    ( "PTTEP", Some ( "PTTEP" ), "PTTEP.BK", AssetClass::SetStock, Currency::Thb, 2, ticks::SET_EQUITY, "PTTE", &[ ] ),
+    ( "PTTGC", Some ( "PTTGC" ), "PTTGC.BK", AssetClass::SetStock, Currency::Thb, 2, ticks::SET_EQUITY, "PTTG", &[ ] ),
+    ( "SAWAD", Some ( "SAWAD" ), "SAWAD.BK", AssetClass::SetStock, Currency::Thb, 2, ticks::SET_EQUITY, "SAWA", &[ ] ),
+    ( "TASCO", Some ( "TASCO" ), "TASCO.BK", AssetClass::SetStock, Currency::Thb, 2, ticks::SET_EQUITY, "TASC", &[ ] ),
+    ( "THANI", Some ( "THANI" ), "THANI.BK", AssetClass::SetStock, Currency::Thb, 2, ticks::SET_EQUITY, "THAN", &[ ] ),
+];
+
+/// Underlying of DWs.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Underlying {
+    /// Canonical symbol, e.g. "SET50", "ADVANC".
+    pub symbol: Box<str>,
+    /// Ticker on SET, if traded on SET.
+    pub set_ticker: Option<Box<str>>,
+    /// RIC of the price that DW tables are quoted on, e.g. the front futures of an index.
+    pub ric: Box<str>,
+    pub asset_class: AssetClass,
+    /// Currency of the underlying price.
+    pub currency: Currency,
+    /// Decimal digits of the quoted underlying price, e.g. 0 for HSI points. The keys of
+    /// [crate::instrument::dw::DWPriceTable::get_underlying_dw_price_table] are in these digits.
+    pub price_digits: u8,
+    /// Tick ladder of the quoted underlying price, e.g. in 1 point for HSI.
+    pub tick_ladder: TickLadder,
+    /// Underlying part of DW symbols, e.g. "S50", "ADVA".
+    pub dw_prefix: Box<str>,
+}
+
+impl Underlying {
+    /// Returns the underlying of given symbol, in any spelling of the issuers, case insensitive.
+    ///
+    /// Symbols not in the mapping table are taken as SET stocks, with the DW prefix of the first 4
+    /// chars.
+    ///
+    /// # Arguments
+    ///
+    /// * `symbol` - Underlying symbol, e.g. "S50", "SET50*", "ADVA", "ADVANC".
+    pub fn resolve ( symbol: &str ) -> Underlying {
+        let symbol = symbol.trim ( ).to_ascii_uppercase ( );
+        let is_spelling = |spelling: &&str| spelling.eq_ignore_ascii_case ( &symbol );
+        UNDERLYINGS.iter ( )
+            .find ( |( canonical, set_ticker, ric, _, _, _, _, dw_prefix, aliases )|
+                [ *canonical, *ric, *dw_prefix ].iter ( ).any ( is_spelling )
+                    || set_ticker.iter ( ).any ( is_spelling )
+                    || aliases.iter ( ).any ( is_spelling )
+            )
+            .map ( |&( canonical, set_ticker, ric, asset_class, currency, price_digits, tick_ladder, dw_prefix, _ )| Underlying {
+                symbol: canonical.into ( ),
+                set_ticker: set_ticker.map ( Into::into ),
+                ric: ric.into ( ),
+                asset_class,
+                currency,
+                price_digits,
+                tick_ladder,
+                dw_prefix: dw_prefix.into ( ),
+            } )
+            .unwrap_or_else ( || {
+                let ticker = symbol.strip_suffix ( ".BK" ).unwrap_or ( &symbol );
+                Underlying {
+                    symbol: ticker.into ( ),
+                    set_ticker: Some ( ticker.into ( ) ),
+                    ric: format ! ( "{}.BK", ticker ).into_boxed_str ( ),
+                    asset_class: AssetClass::SetStock,
+                    currency: Currency::Thb,
+                    price_digits: crate::DEFAULT_PRICE_DIGIT as u8,
+                    tick_ladder: ticks::SET_EQUITY,
+                    dw_prefix: ticker.chars ( ).take ( 4 ).collect::<String> ( ).into_boxed_str ( ),
+                }
+            } )
+    }
+
+    /// Returns true if the underlying is an index.
+    pub fn is_index ( &self ) -> bool {
+        matches ! ( self.asset_class, AssetClass::Set50Index | AssetClass::ForeignIndex )
+    }
+
+    /// Returns true if the underlying price is not in THB.
+    pub fn is_foreign ( &self ) -> bool {
+        self.currency != Currency::Thb
+    }
+
+    /// Returns the market of the quoted underlying price, by its asset class and currency.
+    pub fn market ( &self ) -> Market {
+        match ( self.asset_class, self.currency ) {
+            ( AssetClass::Set50Index, _ ) => Market::Tfex,
+            ( AssetClass::ForeignIndex | AssetClass::ForeignStock, Currency::Hkd ) => Market::Hsi,
+            ( AssetClass::ForeignIndex | AssetClass::ForeignStock, Currency::Usd ) => Market::Spx,
+            _ => Market::Set,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_index ( ) {
+        for symbol in [ "S50", "SET50", "set50*", " SET50 ", "S50cv1" ] {
+            let underlying = Underlying::resolve ( symbol );
+            assert_eq ! ( &*underlying.symbol, "SET50", "{}", symbol );
+            assert_eq ! ( underlying.asset_class, AssetClass::Set50Index );
+            assert_eq ! ( &*underlying.dw_prefix, "S50" );
+        }
+        let underlying = Underlying::resolve ( "S&P500" );
+        assert_eq ! ( ( &*underlying.symbol, &*underlying.ric, underlying.set_ticker.as_deref ( ) ), ( "SPX", "ESc1", None ) );
+        assert ! ( underlying.is_index ( ) );
+        assert_eq ! ( Underlying::resolve ( "HSI" ).asset_class, AssetClass::ForeignIndex );
+    }
+
+    #[test]
+    fn test_tick_ladder_and_market ( ) {
+        for ( symbol, tick_ladder, market ) in [
+            ( "S50", ticks::SET50_INDEX, Market::Tfex ),
+            ( "SET50*", ticks::SET50_INDEX, Market::Tfex ),
+            ( "HANG SENG", ticks::HSI, Market::Hsi ),
+            ( "S&P 500", ticks::SPX, Market::Spx ),
+            ( "ADVA", ticks::SET_EQUITY, Market::Set ),
+            ( "SCB", ticks::SET_EQUITY, Market::Set ),
+        ] {
+            let underlying = Underlying::resolve ( symbol );
+            assert_eq ! ( ( underlying.tick_ladder, underlying.market ( ) ), ( tick_ladder, market ), "{}", symbol );
+            assert_eq ! ( TickLadder::for_underlying ( symbol ), tick_ladder, "{}", symbol );
+            assert_eq ! ( Market::for_underlying ( symbol ), market, "{}", symbol );
+        }
+    }
+
+    #[test]
+    fn test_currency ( ) {
+        let hsi = Underlying::resolve ( "HSI" );
+        assert_eq ! ( ( hsi.currency, hsi.price_digits, hsi.tick_ladder ), ( Currency::Hkd, 0, ticks::HSI ) );
+        assert_eq ! ( Underlying::resolve ( "SPX" ).price_digits, 2 );
+        assert ! ( hsi.is_foreign ( ) );
+        assert_eq ! ( Underlying::resolve ( "SPX" ).currency.to_string ( ), "USD" );
+        assert ! ( ! Underlying::resolve ( "S50" ).is_foreign ( ) );
+        assert ! ( ! Underlying::resolve ( "SCB" ).is_foreign ( ) );
+    }
+
+    #[test]
+    fn test_resolve_set_stock ( ) {
+        let advanc = Underlying::resolve ( "ADVA" );
+        assert_eq ! ( advanc, Underlying {
+            symbol: "ADVANC".into ( ),
+            set_ticker: Some ( "ADVANC".into ( ) ),
+            ric: "ADVANC.BK".into ( ),
+            asset_class: AssetClass::SetStock,
+            currency: Currency::Thb,
+            price_digits: 2,
+            tick_ladder: ticks::SET_EQUITY,
+            dw_prefix: "ADVA".into ( ),
+        } );
+        assert_eq ! ( Underlying::resolve ( "ADVANC" ), advanc );
+        assert_eq ! ( Underlying::resolve ( "ADVANC.BK" ), advanc );
+        assert ! ( ! advanc.is_index ( ) );
+
+        // not in the table
+        let scb = Underlying::resolve ( "scb" );
+        assert_eq ! ( ( &*scb.symbol, &*scb.ric, &*scb.dw_prefix ), ( "SCB", "SCB.BK", "SCB" ) );
+        assert_eq ! ( &*Underlying::resolve ( "BDMS.BK" ).symbol, "BDMS" );
+        assert_eq ! ( &*Underlying::resolve ( "GLOBAL" ).dw_prefix, "GLOB" );
+    }
+}
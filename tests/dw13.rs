@@ -5,6 +5,7 @@ use settrade_dw::{
     dw13::DW13,
 };
 
+use std::str::FromStr;
 use tokio;
 
 // ISSUE: separate the code from `src`?